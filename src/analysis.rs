@@ -0,0 +1,975 @@
+use ::{line_blocks, Cell, Direction, Picross};
+use solve::line_dp;
+
+/// Returns the minimum number of cells needed to hold the blocks of `spec`, that is
+/// the sum of the blocks plus one white cell between each pair of blocks
+pub(crate) fn min_span(spec: &[usize]) -> usize {
+    spec.iter().sum::<usize>() + spec.len().saturating_sub(1)
+}
+
+/// Returns the fraction of the cells of a line of size `size` that take the same
+/// value in all the lines matching `spec`
+fn independence_score(size: usize, spec: &[usize]) -> f64 {
+    match line_dp(&vec![Cell::Unknown; size], spec) {
+        Ok(line) => line.iter().filter(|c| **c != Cell::Unknown).count() as f64 / size as f64,
+        Err(_)   => 0.,
+    }
+}
+
+impl Picross {
+    ///
+    /// Returns the minimum number of cells needed to place the clues of row `row`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]; 2],
+    ///     row_spec: vec![vec![2, 1], vec![]],
+    ///     col_spec: vec![vec![1], vec![1], vec![], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_spec_min_span(0), 4);
+    /// assert_eq!(picross.row_spec_min_span(1), 0);
+    /// ```
+    ///
+    #[doc(alias = "row_clue_total_span")]
+    pub fn row_spec_min_span(&self, row: usize) -> usize {
+        min_span(&self.row_spec[row])
+    }
+
+    ///
+    /// Returns the minimum number of cells needed to place the clues of column `col`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 5,
+    ///     length: 1,
+    ///     cells: vec![vec![Cell::Unknown]; 5],
+    ///     row_spec: vec![vec![1], vec![], vec![1], vec![1], vec![1]],
+    ///     col_spec: vec![vec![1, 3]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.col_spec_min_span(0), 5);
+    /// ```
+    ///
+    #[doc(alias = "col_clue_total_span")]
+    pub fn col_spec_min_span(&self, col: usize) -> usize {
+        min_span(&self.col_spec[col])
+    }
+
+    ///
+    /// Returns the groups of black cells connected by their sides, each as the list
+    /// of the coordinates of its cells
+    ///
+    /// Groups are sorted by their first cell, and the cells of each group are in
+    /// row-major order. Hints are treated as their value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 3,
+    ///     cells: vec![vec![Black, White, Black],
+    ///                 vec![Black, White, Black],
+    ///                 vec![White, Black, Black]],
+    ///     row_spec: vec![vec![1, 1], vec![1, 1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1], vec![3]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.connected_black_regions(), vec![
+    ///     vec![(0, 0), (1, 0)],
+    ///     vec![(0, 2), (1, 2), (2, 1), (2, 2)],
+    /// ]);
+    /// ```
+    ///
+    pub fn connected_black_regions(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut seen = vec![vec![false; self.length]; self.height];
+        let mut regions = vec![];
+
+        for y in 0..self.height {
+            for x in 0..self.length {
+                if seen[y][x] || self.cells[y][x].value() != Cell::Black {
+                    continue;
+                }
+
+                seen[y][x] = true;
+                let mut region = vec![];
+                let mut todo = vec![(y, x)];
+                while let Some((cy, cx)) = todo.pop() {
+                    region.push((cy, cx));
+                    for (ny, nx, c) in self.cell_neighbors(cy, cx) {
+                        if !seen[ny][nx] && c.value() == Cell::Black {
+                            seen[ny][nx] = true;
+                            todo.push((ny, nx));
+                        }
+                    }
+                }
+                region.sort();
+                regions.push(region);
+            }
+        }
+        regions
+    }
+
+    ///
+    /// Returns the blocks of black cells currently in row `row`, as
+    /// `(start, length)` pairs
+    ///
+    /// Unknown cells are treated as white.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 5,
+    ///     cells: vec![vec![Black, Black, White, Unknown, Black],
+    ///                 vec![White, Black, Unknown, Unknown, Unknown]],
+    ///     row_spec: vec![vec![2, 1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![2], vec![], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_blocks(0), vec![(0, 2), (4, 1)]);
+    /// assert_eq!(picross.row_blocks(1), vec![(1, 1)]);
+    /// assert_eq!(picross.col_blocks(1), vec![(0, 2)]);
+    /// assert_eq!(picross.col_blocks(2), vec![]);
+    /// ```
+    ///
+    pub fn row_blocks(&self, row: usize) -> Vec<(usize, usize)> {
+        line_blocks(&self.cells[row])
+    }
+
+    ///
+    /// Returns the blocks of black cells currently in column `col`, as
+    /// `(start, length)` pairs
+    ///
+    /// See `row_blocks` for an example.
+    ///
+    pub fn col_blocks(&self, col: usize) -> Vec<(usize, usize)> {
+        line_blocks(&self.get_line(Direction::Col, col))
+    }
+
+    ///
+    /// Returns the blocks of black cells currently in row `row`, as
+    /// `(start, length, is_closed)` triples
+    ///
+    /// A block is closed if it is bounded by white cells or by the edges of the
+    /// board on both sides. Other blocks touch an unknown cell, and may still grow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 7,
+    ///     cells: vec![vec![Black, Black, White, Black, Unknown, Unknown, Black]],
+    ///     row_spec: vec![vec![2, 2, 1]],
+    ///     col_spec: vec![vec![1], vec![1], vec![], vec![1], vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_blocks_partial(0), vec![(0, 2, true), (3, 1, false), (6, 1, false)]);
+    /// ```
+    ///
+    pub fn row_blocks_partial(&self, row: usize) -> Vec<(usize, usize, bool)> {
+        let line = &self.cells[row];
+        let is_white = |i: usize| line[i].value() == Cell::White;
+        line_blocks(line).into_iter().map(|(start, len)| {
+            let closed = (start == 0 || is_white(start - 1))
+                      && (start + len == line.len() || is_white(start + len));
+            (start, len, closed)
+        }).collect()
+    }
+
+    ///
+    /// Returns the column of the leftmost known cell of row `row`, or `None` if
+    /// all its cells are unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 5,
+    ///     cells: vec![vec![Unknown, White, Unknown, Black, Unknown],
+    ///                 vec![Unknown; 5]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![], vec![], vec![1], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_left_boundary(0), Some(1));
+    /// assert_eq!(picross.row_right_boundary(0), Some(3));
+    /// assert_eq!(picross.row_left_boundary(1), None);
+    /// assert_eq!(picross.row_right_boundary(1), None);
+    /// ```
+    ///
+    pub fn row_left_boundary(&self, row: usize) -> Option<usize> {
+        self.cells[row].iter().position(|c| *c != Cell::Unknown)
+    }
+
+    ///
+    /// Returns the column of the rightmost known cell of row `row`, or `None` if
+    /// all its cells are unknown
+    ///
+    /// See `row_left_boundary` for an example.
+    ///
+    pub fn row_right_boundary(&self, row: usize) -> Option<usize> {
+        self.cells[row].iter().rposition(|c| *c != Cell::Unknown)
+    }
+
+    ///
+    /// Returns the number of free cells left when placing the clues of row `row`,
+    /// that is `length` minus the minimum span of the clues
+    ///
+    /// The smaller the slack, the more cells the overlap technique can force. A
+    /// slack of 0 means the row is completely determined by its clues. Returns 0
+    /// if the clues do not fit in the row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]; 2],
+    ///     row_spec: vec![vec![2, 1], vec![]],
+    ///     col_spec: vec![vec![1], vec![1], vec![], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_slack(0), 1);
+    /// assert_eq!(picross.row_slack(1), 5);
+    /// ```
+    ///
+    pub fn row_slack(&self, row: usize) -> usize {
+        self.length.saturating_sub(self.row_spec_min_span(row))
+    }
+
+    ///
+    /// Returns the number of free cells left when placing the clues of column `col`,
+    /// that is `height` minus the minimum span of the clues
+    ///
+    /// See `row_slack` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 5,
+    ///     length: 1,
+    ///     cells: vec![vec![Cell::Unknown]; 5],
+    ///     row_spec: vec![vec![1], vec![], vec![1], vec![1], vec![1]],
+    ///     col_spec: vec![vec![1, 3]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.col_slack(0), 0);
+    /// ```
+    ///
+    pub fn col_slack(&self, col: usize) -> usize {
+        self.height.saturating_sub(self.col_spec_min_span(col))
+    }
+
+    ///
+    /// Removes the trailing clues of each row and column until the remaining ones
+    /// fit in it
+    ///
+    /// This is a best-effort repair for grids imported from unreliable sources: the
+    /// resulting puzzle may differ from the intended one, but no line has clues
+    /// spanning more cells than it has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![1, 1, 1], vec![4]],
+    ///     col_spec: vec![vec![1], vec![2, 1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// picross.clamp_specs_to_board();
+    /// assert_eq!(picross.row_spec, vec![vec![1, 1], vec![]]);
+    /// assert_eq!(picross.col_spec, vec![vec![1], vec![2], vec![1]]);
+    /// ```
+    ///
+    pub fn clamp_specs_to_board(&mut self) {
+        let (length, height) = (self.length, self.height);
+        let clamp = |spec: &mut Vec<usize>, size: usize| {
+            while min_span(spec) > size {
+                spec.pop();
+            }
+        };
+        for spec in &mut self.row_spec {
+            clamp(spec, length);
+        }
+        for spec in &mut self.col_spec {
+            clamp(spec, height);
+        }
+    }
+
+    ///
+    /// Checks whether the clues of row `row` alone determine all its cells
+    ///
+    /// Neither the current cells nor the columns are taken into account. This is
+    /// the case when the row has no clue, or when its clues exactly span it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 4,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]; 4],
+    ///     row_spec: vec![vec![5], vec![2, 2], vec![], vec![1, 1]],
+    ///     col_spec: vec![vec![2], vec![2], vec![1], vec![2], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.is_row_uniquely_solvable(0));
+    /// assert!(picross.is_row_uniquely_solvable(1));
+    /// assert!(picross.is_row_uniquely_solvable(2));
+    /// assert!(!picross.is_row_uniquely_solvable(3));
+    /// assert_eq!(picross.rows_uniquely_solvable_count(), 3);
+    /// ```
+    ///
+    pub fn is_row_uniquely_solvable(&self, row: usize) -> bool {
+        self.row_spec[row].is_empty() || self.row_spec_min_span(row) == self.length
+    }
+
+    ///
+    /// Returns the number of rows whose clues alone determine all their cells
+    ///
+    /// See `is_row_uniquely_solvable` for details.
+    ///
+    pub fn rows_uniquely_solvable_count(&self) -> usize {
+        (0..self.height).filter(|&y| self.is_row_uniquely_solvable(y)).count()
+    }
+
+    ///
+    /// Checks whether some row has no clue, and must thus be all white
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 3],
+    ///     row_spec: vec![vec![], vec![2], vec![]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.has_empty_spec_row());
+    /// assert!(!picross.has_empty_spec_col());
+    /// assert_eq!(picross.all_white_rows(), vec![0, 2]);
+    /// assert_eq!(picross.all_white_cols(), vec![]);
+    /// ```
+    ///
+    pub fn has_empty_spec_row(&self) -> bool {
+        self.row_spec.iter().any(|s| s.is_empty())
+    }
+
+    ///
+    /// Checks whether some column has no clue, and must thus be all white
+    ///
+    /// See `has_empty_spec_row` for an example.
+    ///
+    pub fn has_empty_spec_col(&self) -> bool {
+        self.col_spec.iter().any(|s| s.is_empty())
+    }
+
+    ///
+    /// Returns the indices of the rows without clues, that must be all white
+    ///
+    /// See `has_empty_spec_row` for an example.
+    ///
+    pub fn all_white_rows(&self) -> Vec<usize> {
+        (0..self.row_spec.len()).filter(|&y| self.row_spec[y].is_empty()).collect()
+    }
+
+    ///
+    /// Returns the indices of the columns without clues, that must be all white
+    ///
+    /// See `has_empty_spec_row` for an example.
+    ///
+    pub fn all_white_cols(&self) -> Vec<usize> {
+        (0..self.col_spec.len()).filter(|&x| self.col_spec[x].is_empty()).collect()
+    }
+
+    ///
+    /// Checks whether the clues of row `row` are non-decreasing
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]; 3],
+    ///     row_spec: vec![vec![1, 1, 2], vec![2, 1], vec![]],
+    ///     col_spec: vec![vec![2], vec![1], vec![1], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.row_spec_is_monotone(0));
+    /// assert!(!picross.row_spec_is_monotone(1));
+    /// assert!(picross.row_spec_is_monotone(2));
+    /// assert!(picross.col_spec_is_monotone(0));
+    /// assert!(!picross.all_specs_monotone());
+    /// ```
+    ///
+    pub fn row_spec_is_monotone(&self, row: usize) -> bool {
+        self.row_spec[row].windows(2).all(|w| w[0] <= w[1])
+    }
+
+    ///
+    /// Checks whether the clues of column `col` are non-decreasing
+    ///
+    /// See `row_spec_is_monotone` for an example.
+    ///
+    pub fn col_spec_is_monotone(&self, col: usize) -> bool {
+        self.col_spec[col].windows(2).all(|w| w[0] <= w[1])
+    }
+
+    ///
+    /// Checks whether the clues of all the rows and columns are non-decreasing
+    ///
+    /// See `row_spec_is_monotone` for an example.
+    ///
+    pub fn all_specs_monotone(&self) -> bool {
+        (0..self.row_spec.len()).all(|y| self.row_spec_is_monotone(y))
+            && (0..self.col_spec.len()).all(|x| self.col_spec_is_monotone(x))
+    }
+
+    ///
+    /// Returns the number of clues of row or column `idx`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]],
+    ///     row_spec: vec![vec![1, 1]],
+    ///     col_spec: vec![vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.line_clue_count(Direction::Row, 0), 2);
+    /// assert_eq!(picross.line_clue_count(Direction::Col, 1), 0);
+    /// ```
+    ///
+    pub fn line_clue_count(&self, dir: Direction, idx: usize) -> usize {
+        self.line_spec(dir, idx).len()
+    }
+
+    ///
+    /// Returns the number of clues of all the rows and columns
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]],
+    ///     row_spec: vec![vec![1, 1]],
+    ///     col_spec: vec![vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.total_clue_count(), 4);
+    /// ```
+    ///
+    pub fn total_clue_count(&self) -> usize {
+        self.row_spec.iter().chain(self.col_spec.iter()).map(|s| s.len()).sum()
+    }
+
+    ///
+    /// Returns the largest number of clues of a single row, or 0 if there is no row
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![1, 1], vec![3]],
+    ///     col_spec: vec![vec![2], vec![1], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.max_clues_in_any_row(), 2);
+    /// ```
+    ///
+    pub fn max_clues_in_any_row(&self) -> usize {
+        self.row_spec.iter().map(|s| s.len()).max().unwrap_or(0)
+    }
+
+    ///
+    /// Returns the largest clue of row `row`, or 0 if the row has no clue
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 4,
+    ///     cells: vec![vec![Cell::Unknown; 4]; 2],
+    ///     row_spec: vec![vec![1, 2], vec![]],
+    ///     col_spec: vec![vec![1], vec![], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_spec_max_block(0), 2);
+    /// assert_eq!(picross.row_spec_max_block(1), 0);
+    /// assert_eq!(picross.col_spec_max_block(3), 1);
+    /// assert_eq!(picross.global_max_block(), 2);
+    /// ```
+    ///
+    pub fn row_spec_max_block(&self, row: usize) -> usize {
+        self.row_spec[row].iter().cloned().max().unwrap_or(0)
+    }
+
+    ///
+    /// Returns the largest clue of column `col`, or 0 if the column has no clue
+    ///
+    /// See `row_spec_max_block` for an example.
+    ///
+    pub fn col_spec_max_block(&self, col: usize) -> usize {
+        self.col_spec[col].iter().cloned().max().unwrap_or(0)
+    }
+
+    ///
+    /// Returns the largest clue of all the rows and columns, or 0 if there is no
+    /// clue
+    ///
+    /// See `row_spec_max_block` for an example.
+    ///
+    pub fn global_max_block(&self) -> usize {
+        self.row_spec.iter().chain(self.col_spec.iter()).flatten().cloned().max().unwrap_or(0)
+    }
+
+    ///
+    /// Returns the number of black cells required by the clues of row `row`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![1, 1], vec![3]],
+    ///     col_spec: vec![vec![2], vec![1], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_clue_sum(0), 2);
+    /// assert_eq!(picross.col_clue_sum(2), 2);
+    /// assert_eq!(picross.total_black_required(), 5);
+    /// ```
+    ///
+    pub fn row_clue_sum(&self, row: usize) -> usize {
+        self.row_spec[row].iter().sum()
+    }
+
+    ///
+    /// Returns the number of black cells required by the clues of column `col`
+    ///
+    /// See `row_clue_sum` for an example.
+    ///
+    pub fn col_clue_sum(&self, col: usize) -> usize {
+        self.col_spec[col].iter().sum()
+    }
+
+    ///
+    /// Returns the number of black cells required by the clues of all the rows
+    ///
+    /// For a valid puzzle, this is also the number of black cells required by the
+    /// clues of all the columns. See `row_clue_sum` for an example.
+    ///
+    pub fn total_black_required(&self) -> usize {
+        self.row_spec.iter().flatten().sum()
+    }
+
+    ///
+    /// Checks whether the rows and the columns require the same number of black
+    /// cells
+    ///
+    /// This is a necessary condition for the puzzle to have a solution, and a
+    /// cheap first check for puzzle validators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![1]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// assert!(picross.validate_row_col_black_counts_match());
+    ///
+    /// picross.col_spec[1] = vec![];
+    /// assert!(!picross.validate_row_col_black_counts_match());
+    /// ```
+    ///
+    pub fn validate_row_col_black_counts_match(&self) -> bool {
+        self.total_black_required() == self.col_spec.iter().flatten().sum::<usize>()
+    }
+
+    ///
+    /// Returns the fraction of the cells that the clues require to be black
+    ///
+    /// Very sparse or very dense puzzles are more likely to have a unique
+    /// solution, which makes this a useful metric to tune the `density` given to
+    /// `generate_unique_random`. Returns 0 for an empty grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 4,
+    ///     cells: vec![vec![Cell::Unknown; 4]; 2],
+    ///     row_spec: vec![vec![1, 1], vec![4]],
+    ///     col_spec: vec![vec![2], vec![1], vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.spec_density(), 0.75);
+    /// ```
+    ///
+    pub fn spec_density(&self) -> f64 {
+        let total = self.height * self.length;
+        if total == 0 {
+            return 0.;
+        }
+        self.total_black_required() as f64 / total as f64
+    }
+
+    ///
+    /// Returns the base 2 logarithm of the product, over all the rows and columns,
+    /// of the number of ways to place their clues
+    ///
+    /// The current cells are not taken into account. A score of 0 means each line
+    /// can only be filled one way, so that the solution is unique and trivial, and
+    /// each bit more doubles the number of combinations of lines to consider. A
+    /// low score is thus a good sign that the solution is unique, but not a proof.
+    /// Returns infinity if the clues of some line do not fit in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// // Each column can be filled in 2 ways
+    /// assert_eq!(picross.spec_ambiguity_score(), 2.);
+    ///
+    /// picross.row_spec = vec![vec![2], vec![2]];
+    /// picross.col_spec = vec![vec![2], vec![2]];
+    /// assert_eq!(picross.spec_ambiguity_score(), 0.);
+    /// ```
+    ///
+    pub fn spec_ambiguity_score(&self) -> f64 {
+        // A line of `size` cells has C(slack + k, k) placements of its k clues
+        let log2_placements = |size: usize, spec: &Vec<usize>| {
+            let span = min_span(spec);
+            if span > size {
+                return f64::INFINITY;
+            }
+            let n = size - span + spec.len();
+            (1..spec.len() + 1).map(|i| ((n - spec.len() + i) as f64 / i as f64).log2()).sum::<f64>()
+        };
+
+        self.row_spec.iter().map(|s| log2_placements(self.length, s))
+            .chain(self.col_spec.iter().map(|s| log2_placements(self.height, s)))
+            .sum()
+    }
+
+    ///
+    /// Returns the fraction of the cells of row `row` that are forced by the row
+    /// specification alone, without looking at the columns nor at the current cells
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]; 3],
+    ///     row_spec: vec![vec![5], vec![3], vec![]],
+    ///     col_spec: vec![vec![2], vec![2], vec![2], vec![2], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_independence_score(0), 1.);
+    /// assert_eq!(picross.row_independence_score(1), 0.2);
+    /// assert_eq!(picross.row_independence_score(2), 1.);
+    /// ```
+    ///
+    pub fn row_independence_score(&self, row: usize) -> f64 {
+        independence_score(self.length, &self.row_spec[row])
+    }
+
+    ///
+    /// Returns the fraction of the cells of column `col` that are forced by the
+    /// column specification alone, without looking at the rows nor at the current
+    /// cells
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 3],
+    ///     row_spec: vec![vec![1], vec![], vec![1]],
+    ///     col_spec: vec![vec![1, 1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.col_independence_score(0), 1.);
+    /// assert_eq!(picross.col_independence_score(1), 1.);
+    /// ```
+    ///
+    pub fn col_independence_score(&self, col: usize) -> f64 {
+        independence_score(self.height, &self.col_spec[col])
+    }
+
+    ///
+    /// Returns the index of the row with the highest independence score, that is
+    /// the best "anchor" row to start solving from
+    ///
+    /// If several rows have the same score, the first one is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the picross grid has no row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]; 3],
+    ///     row_spec: vec![vec![1], vec![3], vec![4]],
+    ///     col_spec: vec![vec![1], vec![2], vec![2], vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.most_independent_row(), 2);
+    /// ```
+    ///
+    pub fn most_independent_row(&self) -> usize {
+        if self.height == 0 {
+            panic!("Not supporting empty picross grids!");
+        }
+        let scores = (0..self.height).map(|r| self.row_independence_score(r))
+                                     .collect::<Vec<f64>>();
+        let mut best = 0;
+        for (r, &score) in scores.iter().enumerate() {
+            if score > scores[best] {
+                best = r;
+            }
+        }
+        best
+    }
+
+    ///
+    /// Returns a human-readable summary of the grid, for puzzle authors and debugging
+    ///
+    /// It contains the dimensions, the number of cells already filled, the number
+    /// of clues, the number of black cells required by the rows and by the columns,
+    /// the number of cells forced by the specification of their row or column alone,
+    /// and an estimation of the difficulty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![3], vec![1]],
+    ///     col_spec: vec![vec![1], vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(
+    ///     picross.statistics(),
+    ///     "Dimensions: 2 rows x 3 columns (6 cells)\n".to_string() +
+    ///     "Filled cells: 0 (0.0%)\n" +
+    ///     "Clues: 2 in rows, 3 in columns\n" +
+    ///     "Black cells required: 4 by rows, 4 by columns\n" +
+    ///     "Cells forced by overlap: 4\n" +
+    ///     "Estimated difficulty: line-solvable\n"
+    /// );
+    /// ```
+    ///
+    pub fn statistics(&self) -> String {
+        let total = self.height * self.length;
+        let filled = self.cells.iter()
+                               .map(|r| r.iter().filter(|c| **c != Cell::Unknown).count())
+                               .sum::<usize>();
+        let count_clues = |specs: &Vec<Vec<usize>>| specs.iter().map(|s| s.len()).sum::<usize>();
+        let row_blacks = self.total_black_required();
+        let col_blacks = self.col_spec.iter().flatten().sum::<usize>();
+
+        let mut res = String::new();
+        res.push_str(&format!("Dimensions: {} rows x {} columns ({} cells)\n",
+                              self.height, self.length, total));
+        res.push_str(&format!("Filled cells: {} ({:.1}%)\n",
+                              filled, if total == 0 { 0. } else { 100. * filled as f64 / total as f64 }));
+        res.push_str(&format!("Clues: {} in rows, {} in columns\n",
+                              count_clues(&self.row_spec), count_clues(&self.col_spec)));
+        res.push_str(&format!("Black cells required: {} by rows, {} by columns{}\n",
+                              row_blacks, col_blacks,
+                              if self.validate_row_col_black_counts_match() { "" } else { " (mismatch!)" }));
+
+        if self.height != self.row_spec.len() || self.length != self.col_spec.len() {
+            res.push_str("Inconsistent dimensions!\n");
+            return res;
+        }
+
+        let mut forced = vec![vec![false; self.length]; self.height];
+        for (y, spec) in self.row_spec.iter().enumerate() {
+            if let Ok(line) = line_dp(&vec![Cell::Unknown; self.length], spec) {
+                for (x, c) in line.iter().enumerate() {
+                    forced[y][x] |= *c != Cell::Unknown;
+                }
+            }
+        }
+        for (x, spec) in self.col_spec.iter().enumerate() {
+            if let Ok(line) = line_dp(&vec![Cell::Unknown; self.height], spec) {
+                for (y, c) in line.iter().enumerate() {
+                    forced[y][x] |= *c != Cell::Unknown;
+                }
+            }
+        }
+        res.push_str(&format!("Cells forced by overlap: {}\n",
+                              forced.iter().map(|r| r.iter().filter(|f| **f).count())
+                                           .sum::<usize>()));
+
+        let mut solved = self.clone();
+        let difficulty = match solved.propagate() {
+            Err(_) => "unsolvable",
+            Ok(_) if solved.cells.iter().all(|r| r.iter().all(|c| *c != Cell::Unknown)) => "line-solvable",
+            Ok(_) => "requires guessing",
+        };
+        res.push_str(&format!("Estimated difficulty: {}\n", difficulty));
+
+        res
+    }
+
+    ///
+    /// Prints the summary returned by `statistics` on the standard output
+    ///
+    pub fn print_statistics(&self) {
+        print!("{}", self.statistics());
+    }
+}
@@ -1,8 +1,9 @@
 use ::{Cell, Picross};
+use analysis::min_span;
 
 /// Iterator yielding all increasing series from 0..n to 0..size
 /// Ex: if size==3 and n==2, the iterator yield
-/// ```
+/// ```text
 /// vec![0,1], vec[0,2], vec[1,2]
 /// ```
 /// This iterator should be created with gen_increasing_series
@@ -21,6 +22,11 @@ impl Iterator for IncreasingSeriesGenerator {
             return Some(self.series.clone());
         }
 
+        if self.n == 0 {
+            // la seule série vide a déjà été renvoyée
+            return None;
+        }
+
         let mut i = self.n - 1;
         loop {
             if self.series[i] < (if i == self.n - 1 {self.size} else {self.series[i+1]}) - 1 {
@@ -43,7 +49,7 @@ impl Iterator for IncreasingSeriesGenerator {
 
 /// Constructor for IncreasingSeriesGenerator
 /// This function returns an iterator yielding all increasing series from 0..n to 0..size
-/// ```
+/// ```text
 /// for series in gen_increasing_series(2, 3) {
 ///     // row will be successively vec![0,1], vec[0,2], vec[1,2]
 /// }
@@ -103,12 +109,21 @@ impl<'a> Iterator for PicrossRowGenerator<'a> {
 /// Returns an iterator yielding all possible picross rows following the given constraints :
 /// row_size: size of the row
 /// spec: specification of the blocks : &vec![1,2] means a one-cell block and a two-cell block
-fn gen_picross_rows(row_size: usize, spec: &Vec<usize>) -> Vec<Vec<Cell>> {
-    PicrossRowGenerator {
-        row_size: row_size,
-        spec: &spec,
-        inc_series_gen: gen_increasing_series(spec.len(), row_size + 1 - spec.iter().fold(0, |sum, x| sum + x))
-    }.collect::<Vec<Vec<Cell>>>()
+pub(crate) fn iter_picross_rows<'a>(row_size: usize, spec: &'a Vec<usize>) -> impl Iterator<Item=Vec<Cell>> + 'a {
+    // si les blocs ne tiennent pas dans la ligne, il n'y a aucune ligne possible
+    let fits = min_span(spec) <= row_size;
+    fits.then(|| PicrossRowGenerator {
+        row_size,
+        spec,
+        inc_series_gen: gen_increasing_series(spec.len(), row_size + 1 - spec.iter().sum::<usize>())
+    }).into_iter().flatten()
+}
+
+/// Returns all possible picross rows following the given constraints :
+/// row_size: size of the row
+/// spec: specification of the blocks : &vec![1,2] means a one-cell block and a two-cell block
+pub(crate) fn gen_picross_rows(row_size: usize, spec: &Vec<usize>) -> Vec<Vec<Cell>> {
+    iter_picross_rows(row_size, spec).collect::<Vec<Vec<Cell>>>()
 }
 
 impl Picross {
@@ -0,0 +1,187 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use ::{Picross, PuzzleMetadata};
+use error::PicrossError;
+use parse::ParseError;
+use pbn::{parse_pbn_puzzleset, pbn_puzzleset};
+use stream::PicrossStreamParser;
+
+/// File format of a puzzle database
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DbFormat {
+    /// A PBN (Puzzle Binary Notation) document with one `<puzzle>` per grid, keeping
+    /// the metadata and the solved cells
+    Pbn,
+    /// The grids in the format of `Picross::to_string_compact`, separated by blank
+    /// lines, keeping only the specifications
+    Compact,
+}
+
+/// Field of `PuzzleMetadata` used to look puzzles up
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MetadataField {
+    Title,
+    Author,
+    Copyright,
+    Description,
+}
+
+impl MetadataField {
+    /// Returns the value of this field in `meta`
+    fn get(self, meta: &PuzzleMetadata) -> Option<&str> {
+        let value = match self {
+            MetadataField::Title       => &meta.title,
+            MetadataField::Author      => &meta.author,
+            MetadataField::Copyright   => &meta.copyright,
+            MetadataField::Description => &meta.description,
+        };
+        value.as_ref().map(|v| v.as_str())
+    }
+}
+
+///
+/// Collection of puzzles along with their metadata, that can be saved to and loaded
+/// from a file
+///
+/// # Examples
+///
+/// ```
+/// use picross::{Picross, Cell, PuzzleMetadata};
+/// use picross::db::{DbFormat, MetadataField, PuzzleDb};
+///
+/// let picross = Picross {
+///     height: 1,
+///     length: 2,
+///     cells: vec![vec![Cell::Black, Cell::White]],
+///     row_spec: vec![vec![1]],
+///     col_spec: vec![vec![1], vec![]],
+///     possible_rows: vec![],
+///     possible_cols: vec![],
+/// };
+/// let meta = PuzzleMetadata {
+///     title: Some("Dot".to_string()),
+///     ..PuzzleMetadata::default()
+/// };
+///
+/// let mut db = PuzzleDb::new();
+/// db.insert(meta.clone(), picross.clone());
+/// db.insert(PuzzleMetadata::default(), picross.clone());
+/// assert_eq!(db.len(), 2);
+/// assert_eq!(db.find_by(MetadataField::Title, "Dot").len(), 1);
+///
+/// let mut copy = PuzzleDb::new();
+/// assert_eq!(copy.import_all(&db.export_all(DbFormat::Pbn), DbFormat::Pbn).unwrap(), 2);
+/// let (ref found_meta, ref found) = copy.find_by(MetadataField::Title, "Dot")[0];
+/// assert_eq!(*found_meta, meta);
+/// assert_eq!(found.cells, picross.cells);
+///
+/// let (removed_meta, _) = copy.remove(0);
+/// assert_eq!(removed_meta, meta);
+/// assert!(copy.find_by(MetadataField::Title, "Dot").is_empty());
+///
+/// let path = std::env::temp_dir().join("picross-db-example.txt");
+/// db.save_to_file(&path, DbFormat::Compact).unwrap();
+/// let loaded = PuzzleDb::load_from_file(&path, DbFormat::Compact).unwrap();
+/// # std::fs::remove_file(&path).unwrap();
+/// assert_eq!(loaded.len(), 2);
+/// assert_eq!(loaded.puzzles[1].1.col_spec, picross.col_spec);
+/// ```
+///
+#[derive(Clone, Default, Debug)]
+pub struct PuzzleDb {
+    pub puzzles: Vec<(PuzzleMetadata, Picross)>,
+}
+
+impl PuzzleDb {
+    /// Creates an empty database
+    pub fn new() -> PuzzleDb {
+        PuzzleDb { puzzles: vec![] }
+    }
+
+    /// Returns the number of puzzles in the database
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    /// Checks whether the database holds no puzzle
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+
+    /// Adds a puzzle at the end of the database, and returns its index
+    pub fn insert(&mut self, meta: PuzzleMetadata, picross: Picross) -> usize {
+        self.puzzles.push((meta, picross));
+        self.puzzles.len() - 1
+    }
+
+    ///
+    /// Removes the puzzle at index `index` and returns it, shifting the following
+    /// puzzles
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    pub fn remove(&mut self, index: usize) -> (PuzzleMetadata, Picross) {
+        self.puzzles.remove(index)
+    }
+
+    /// Returns the puzzles whose metadata field `field` is exactly `value`
+    pub fn find_by(&self, field: MetadataField, value: &str) -> Vec<&(PuzzleMetadata, Picross)> {
+        self.puzzles.iter().filter(|&(meta, _)| field.get(meta) == Some(value)).collect()
+    }
+
+    /// Serializes all the puzzles in `format`
+    pub fn export_all(&self, format: DbFormat) -> String {
+        match format {
+            DbFormat::Pbn => {
+                let puzzles = self.puzzles.iter().map(|(m, p)| (m, p)).collect::<Vec<_>>();
+                pbn_puzzleset(&puzzles)
+            }
+            DbFormat::Compact => {
+                self.puzzles.iter().map(|(_, p)| p.to_string_compact() + "\n")
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            }
+        }
+    }
+
+    ///
+    /// Parses puzzles serialized in `format` and adds them at the end of the
+    /// database, returning how many were added
+    ///
+    /// Nothing is added if parsing fails. With `DbFormat::Compact`, the metadata is
+    /// empty and the cells are all `Cell::Unknown`.
+    ///
+    pub fn import_all(&mut self, s: &str, format: DbFormat) -> Result<usize, ParseError> {
+        let puzzles = match format {
+            DbFormat::Pbn => {
+                parse_pbn_puzzleset(s)?.into_iter().map(|(p, m)| (m, p)).collect::<Vec<_>>()
+            }
+            DbFormat::Compact => {
+                PicrossStreamParser::new(Cursor::new(s))
+                    .map(|p| p.map(|p| (PuzzleMetadata::default(), p)))
+                    .collect::<Result<Vec<_>, ParseError>>()?
+            }
+        };
+        let added = puzzles.len();
+        self.puzzles.extend(puzzles);
+        Ok(added)
+    }
+
+    /// Writes all the puzzles to the file at `path` in `format`, replacing its
+    /// content
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P, format: DbFormat) -> Result<(), PicrossError> {
+        fs::write(path, self.export_all(format))?;
+        Ok(())
+    }
+
+    /// Reads a database from the file at `path`, written in `format`
+    pub fn load_from_file<P: AsRef<Path>>(path: P, format: DbFormat) -> Result<PuzzleDb, PicrossError> {
+        let mut db = PuzzleDb::new();
+        db.import_all(&fs::read_to_string(path)?, format)?;
+        Ok(db)
+    }
+}
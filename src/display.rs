@@ -1,7 +1,8 @@
 use std::iter;
 use std::fmt::{Formatter, Display, Result, Write};
 
-use ::{Cell, Picross};
+use ::{spec_to_string, Cell, Picross};
+use origin::CellOrigin;
 
 impl Picross {
     ///
@@ -60,6 +61,358 @@ impl Picross {
              .max()
              .expect("Not supporting empty picross grids!")
     }
+
+    ///
+    /// /!\ Intended for internal use only /!\
+    ///
+    /// Converts a line of cells into its textual representation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// assert_eq!(
+    ///     Picross::cells_to_string(&vec![Cell::Black, Cell::White, Cell::Unknown,
+    ///                                    Cell::Hint(true), Cell::Hint(false)]),
+    ///     "# ?bw"
+    /// );
+    /// ```
+    ///
+    pub fn cells_to_string(cells: &[Cell]) -> String {
+        cells.iter().map(|c| match *c {
+            Cell::Unknown     => '?',
+            Cell::White       => ' ',
+            Cell::Black       => '#',
+            Cell::Hint(false) => 'w',
+            Cell::Hint(true)  => 'b',
+        }).collect()
+    }
+
+    ///
+    /// Renders two boards next to each other
+    ///
+    /// The left board is rendered just like with `Display`, and the cells of the
+    /// right board are drawn on the same lines, after a gap. Clues are only shown
+    /// for the left board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `left` and `right` do not have the same dimensions, or if `height`
+    /// or `length` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let attempt = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     row_spec: vec![vec![1, 1], vec![2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![2]],
+    ///     cells: vec![vec![Cell::Black  , Cell::White, Cell::Unknown],
+    ///                 vec![Cell::Unknown, Cell::Black, Cell::Black  ]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let mut solution = attempt.clone();
+    /// solution.cells = vec![vec![Cell::Black, Cell::White, Cell::Black],
+    ///                       vec![Cell::White, Cell::Black, Cell::Black]];
+    ///
+    /// let res =
+    ///     "   |112\n".to_string() +
+    ///     "---+---   ---\n" +
+    ///     "1 1|# ?   # #\n" +
+    ///     "  2|?##    ##\n";
+    /// assert_eq!(Picross::side_by_side(&attempt, &solution), res);
+    /// ```
+    ///
+    pub fn side_by_side(left: &Picross, right: &Picross) -> String {
+        if left.height != right.height || left.length != right.length {
+            panic!("Cannot render side by side boards of different dimensions!");
+        }
+
+        let gap = "   ";
+        let rendered = left.to_string();
+        let lines = rendered.lines().collect::<Vec<&str>>();
+        let header_len = lines.len() - left.height - 1;
+
+        let mut res = String::new();
+
+        // Clue header: only for the left board
+        for line in &lines[..header_len] {
+            res.push_str(line);
+            res.push('\n');
+        }
+
+        // Header separator
+        res.push_str(lines[header_len]);
+        res.push_str(gap);
+        res.push_str(&"-".repeat(right.length));
+        res.push('\n');
+
+        for (line, cells) in lines[header_len + 1..].iter().zip(right.cells.iter()) {
+            res.push_str(line);
+            res.push_str(gap);
+            res.push_str(&Picross::cells_to_string(cells));
+            res.push('\n');
+        }
+
+        res
+    }
+
+    ///
+    /// Renders the cells with gridlines between every two adjacent cells, like a
+    /// spreadsheet, which makes large boards easier to read
+    ///
+    /// Cells are drawn like with `Display`, but the clues are not shown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Black  , Cell::White, Cell::Black],
+    ///                 vec![Cell::Unknown, Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1, 1], vec![2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let res =
+    ///     "#│ │#\n".to_string() +
+    ///     "─┼─┼─\n" +
+    ///     "?│#│#\n";
+    /// assert_eq!(picross.display_with_grid_lines(), res);
+    /// ```
+    ///
+    pub fn display_with_grid_lines(&self) -> String {
+        let separator = vec!["─"; self.length].join("┼");
+        self.cells.iter()
+            .map(|row| {
+                row.iter().map(|c| Picross::cells_to_string(&[*c])).collect::<Vec<String>>().join("│")
+            })
+            .map(|line| line + "\n")
+            .collect::<Vec<String>>()
+            .join(&(separator + "\n"))
+    }
+
+    ///
+    /// Renders the cells, one row per line, with the given characters
+    ///
+    /// The clues are not shown, and hints are drawn as their value. With
+    /// `('#', ' ', '?')`, this gives the cells drawn by `Display`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Unknown, Cell::Hint(true)]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.to_grid_string('▓', '░', '·'), "▓░\n·▓\n");
+    /// ```
+    ///
+    pub fn to_grid_string(&self, black: char, white: char, unknown: char) -> String {
+        let mut res = String::new();
+        for row in &self.cells {
+            res.extend(row.iter().map(|c| match c.value() {
+                Cell::Black => black,
+                Cell::White => white,
+                _           => unknown,
+            }));
+            res.push('\n');
+        }
+        res
+    }
+
+    ///
+    /// Serializes the specifications in the format read by `parse`, without any
+    /// unneeded whitespace
+    ///
+    /// Identical puzzles always give identical strings, which makes this suitable
+    /// for deduplication. The cells are not serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![1, 1], vec![]],
+    ///     col_spec: vec![vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let compact = picross.to_string_compact();
+    /// assert_eq!(compact, "2\n3\n[1,1]\n[]\n[1]\n[]\n[1]");
+    ///
+    /// let parsed = Picross::parse(&mut compact.lines());
+    /// assert_eq!(parsed.row_spec, picross.row_spec);
+    /// assert_eq!(parsed.col_spec, picross.col_spec);
+    /// ```
+    ///
+    pub fn to_string_compact(&self) -> String {
+        let mut lines = vec![self.height.to_string(), self.length.to_string()];
+        lines.extend(self.row_spec.iter().map(|s| spec_to_string(s)));
+        lines.extend(self.col_spec.iter().map(|s| spec_to_string(s)));
+        lines.join("\n")
+    }
+
+    ///
+    /// Summarizes the specifications on a single line, for logging
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![1, 1], vec![]],
+    ///     col_spec: vec![vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.format_compact_spec(), "R:[1,1],[] C:[1],[],[1]");
+    /// ```
+    ///
+    pub fn format_compact_spec(&self) -> String {
+        let join = |specs: &Vec<Vec<usize>>| {
+            specs.iter().map(|s| spec_to_string(s)).collect::<Vec<String>>().join(",")
+        };
+        format!("R:{} C:{}", join(&self.row_spec), join(&self.col_spec))
+    }
+
+    ///
+    /// Builds a regular expression matching the rows that follow the specification
+    /// of row `row`, written with `0` for white cells and `1` for black cells
+    ///
+    /// A lookahead fixes the length of the row, and the gaps between blocks are
+    /// bounded by the slack of the row, so the expression matches exactly the valid
+    /// rows. If the clues do not fit in the row, it matches nothing. The lookahead
+    /// needs an engine supporting it, like PCRE or the `fancy-regex` crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate fancy_regex;
+    /// extern crate picross;
+    ///
+    /// use fancy_regex::Regex;
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]; 3],
+    ///     row_spec: vec![vec![2, 1], vec![], vec![3, 2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_spec_to_regex(0), "^(?=[01]{5}$)0{0,1}1{2}0{1,2}1{1}0{0,1}$");
+    /// assert_eq!(picross.row_spec_to_regex(1), "^0{5}$");
+    ///
+    /// let re = Regex::new(&picross.row_spec_to_regex(0)).unwrap();
+    /// assert!(re.is_match("11010").unwrap());
+    /// assert!(re.is_match("01101").unwrap());
+    /// assert!(!re.is_match("0110010").unwrap());
+    /// assert!(!re.is_match("11100").unwrap());
+    ///
+    /// let too_long = Regex::new(&picross.row_spec_to_regex(2)).unwrap();
+    /// assert!(!too_long.is_match("11101").unwrap());
+    /// assert!(!too_long.is_match("111011").unwrap());
+    /// ```
+    ///
+    pub fn row_spec_to_regex(&self, row: usize) -> String {
+        let spec = &self.row_spec[row];
+        if spec.is_empty() {
+            return format!("^0{{{}}}$", self.length);
+        }
+
+        let slack = self.row_slack(row);
+        let blocks = spec.iter().map(|n| format!("1{{{}}}", n)).collect::<Vec<String>>();
+        format!("^(?=[01]{{{}}}$)0{{0,{}}}{}0{{0,{}}}$", self.length, slack,
+                blocks.join(&format!("0{{1,{}}}", slack + 1)), slack)
+    }
+
+    ///
+    /// Serializes the board to the GraphViz `.dot` format, for debugging
+    ///
+    /// Each cell is a node labelled with its coordinates (row first) and its value,
+    /// and edges connect horizontally and vertically adjacent cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::Unknown]],
+    ///     row_spec: vec![vec![1]],
+    ///     col_spec: vec![vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let res =
+    ///     "graph picross {\n".to_string() +
+    ///     "    c_0_0 [label=\"(0, 0): Black\"];\n" +
+    ///     "    c_0_1 [label=\"(0, 1): Unknown\"];\n" +
+    ///     "    c_0_0 -- c_0_1;\n" +
+    ///     "}\n";
+    /// assert_eq!(picross.to_dot_notation(), res);
+    /// ```
+    ///
+    pub fn to_dot_notation(&self) -> String {
+        let mut res = "graph picross {\n".to_string();
+
+        for (y, row) in self.cells.iter().enumerate() {
+            for (x, c) in row.iter().enumerate() {
+                res.push_str(&format!("    c_{}_{} [label=\"({}, {}): {:?}\"];\n", y, x, y, x, c));
+            }
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.length {
+                if x + 1 < self.length {
+                    res.push_str(&format!("    c_{}_{} -- c_{}_{};\n", y, x, y, x + 1));
+                }
+                if y + 1 < self.height {
+                    res.push_str(&format!("    c_{}_{} -- c_{}_{};\n", y, x, y + 1, x));
+                }
+            }
+        }
+
+        res.push_str("}\n");
+        res
+    }
 }
 
 impl Display for Picross {
@@ -107,8 +460,137 @@ impl Display for Picross {
     /// ```
     ///
     fn fmt(&self, f: &mut Formatter) -> Result {
-        let row_spec = Picross::specs_to_strings(&self.row_spec);
-        let col_spec = Picross::specs_to_strings(&self.col_spec);
+        PicrossPrinter::new(self).fmt(f)
+    }
+}
+
+/// How `PicrossPrinter` draws the cells
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisplayStyle {
+    /// `#` for black cells, a space for white cells and `?` for unknown cells, like
+    /// `Display`
+    Default,
+    /// Like `Default`, but with `.` for white cells
+    Dots,
+}
+
+/// Configurable rendering of a Picross grid, implementing `Display`
+///
+/// # Examples
+///
+/// ```
+/// use picross::{Picross, Cell};
+/// use picross::display::{DisplayStyle, PicrossPrinter};
+///
+/// let picross = Picross {
+///     height: 2,
+///     length: 2,
+///     cells: vec![vec![Cell::Black, Cell::White],
+///                 vec![Cell::Unknown, Cell::Black]],
+///     row_spec: vec![vec![1], vec![1]],
+///     col_spec: vec![vec![1], vec![1]],
+///     possible_rows: vec![],
+///     possible_cols: vec![],
+/// };
+///
+/// assert_eq!(format!("{}", PicrossPrinter::new(&picross)), format!("{}", picross));
+/// assert_eq!(
+///     format!("{}", PicrossPrinter::new(&picross).with_style(DisplayStyle::Dots).without_margin()),
+///     "#.\n?#\n"
+/// );
+/// assert_eq!(
+///     format!("{}", PicrossPrinter::new(&picross).with_ansi(true).without_margin()),
+///     "\x1b[7m#\x1b[0m \n\x1b[2m?\x1b[0m\x1b[7m#\x1b[0m\n"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PicrossPrinter<'a> {
+    pub board: &'a Picross,
+    pub style: DisplayStyle,
+    /// Whether the clues are drawn around the cells
+    pub margin: bool,
+    /// Whether black and unknown cells are highlighted with ANSI escape codes
+    pub ansi: bool,
+    /// How each cell was found, used to color the cells when `ansi` is set
+    pub origins: Option<&'a [Vec<CellOrigin>]>,
+}
+
+impl<'a> PicrossPrinter<'a> {
+    /// Creates a printer rendering `board` like `Display`
+    pub fn new(board: &'a Picross) -> PicrossPrinter<'a> {
+        PicrossPrinter {
+            board,
+            style: DisplayStyle::Default,
+            margin: true,
+            ansi: false,
+            origins: None,
+        }
+    }
+
+    /// Sets the way cells are drawn
+    pub fn with_style(mut self, style: DisplayStyle) -> PicrossPrinter<'a> {
+        self.style = style;
+        self
+    }
+
+    /// Sets whether black and unknown cells are highlighted with ANSI escape codes
+    pub fn with_ansi(mut self, ansi: bool) -> PicrossPrinter<'a> {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Colors the cells with ANSI escape codes according to how they were found, as
+    /// returned by `Picross::solve_by_color_coding`
+    pub fn with_origins(mut self, origins: &'a [Vec<CellOrigin>]) -> PicrossPrinter<'a> {
+        self.origins = Some(origins);
+        self.ansi = true;
+        self
+    }
+
+    /// Draws only the cells, without the clues
+    pub fn without_margin(mut self) -> PicrossPrinter<'a> {
+        self.margin = false;
+        self
+    }
+
+    /// Converts row `y` of the board into its textual representation
+    fn row_to_string(&self, y: usize) -> String {
+        self.board.cells[y].iter().enumerate().map(|(x, &c)| {
+            let ch = match (self.style, c) {
+                (DisplayStyle::Dots, Cell::White) => ".".to_string(),
+                _                                 => Picross::cells_to_string(&[c]),
+            };
+            if !self.ansi {
+                return ch;
+            }
+            let color = match self.origins {
+                Some(origins) => origins[y][x].ansi_color(),
+                None => match c.value() {
+                    Cell::Black   => Some("\x1b[7m"),
+                    Cell::Unknown => Some("\x1b[2m"),
+                    _             => None,
+                },
+            };
+            match color {
+                Some(color) => format!("{}{}\x1b[0m", color, ch),
+                None        => ch,
+            }
+        }).collect()
+    }
+}
+
+impl<'a> Display for PicrossPrinter<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if !self.margin {
+            for y in 0..self.board.height {
+                f.write_str(&self.row_to_string(y))?;
+                f.write_char('\n')?;
+            }
+            return Ok(());
+        }
+
+        let row_spec = Picross::specs_to_strings(&self.board.row_spec);
+        let col_spec = Picross::specs_to_strings(&self.board.col_spec);
 
         let max_rs_len = Picross::max_len_non_empty(&row_spec);
         let max_cs_len = Picross::max_len_non_empty(&col_spec);
@@ -128,21 +610,17 @@ impl Display for Picross {
         // Write header separator
         try!(f.write_str(&iter::repeat('-').take(max_rs_len).collect::<String>()));
         try!(f.write_char('+'));
-        try!(f.write_str(&iter::repeat('-').take(self.length).collect::<String>()));
+        try!(f.write_str(&iter::repeat('-').take(self.board.length).collect::<String>()));
         try!(f.write_char('\n'));
 
-        for i in 0..self.height {
+        for i in 0..self.board.height {
             // Write row specs
             try!(f.write_str(&iter::repeat(' ').take(max_rs_len - row_spec[i].len()).collect::<String>()));
             try!(f.write_str(&row_spec[i]));
             try!(f.write_char('|'));
 
             // Write actual content
-            try!(f.write_str(&self.cells[i].iter().map(|c| match *c {
-                Cell::Unknown => '?',
-                Cell::White   => ' ',
-                Cell::Black   => '#'
-            }).collect::<String>()));
+            try!(f.write_str(&self.row_to_string(i)));
 
             // Okay, let's continue
             try!(f.write_char('\n'));
@@ -0,0 +1,271 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+use ::{Cell, Picross};
+use error::DecodeError;
+use parse::ParseError;
+
+/// Largest height or length accepted by `parse_from_url_param`, so that a short
+/// parameter cannot make it allocate a huge grid
+const MAX_URL_DIMENSION: usize = 1000;
+
+/// Returns the 2-bit code of `c`, hints being encoded as their value
+fn cell_code(c: Cell) -> u8 {
+    match c.value() {
+        Cell::Black => 1,
+        Cell::White => 2,
+        _           => 0,
+    }
+}
+
+/// Appends `n` to `bytes` as a variable-length integer: 7 bits per byte, least
+/// significant first, the high bit being set on all bytes but the last
+fn push_varint(bytes: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        bytes.push((n & 0x7f) as u8 | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+/// Returns whether the clues of `spec` fit in a line of `len` cells
+fn spec_fits(spec: &[usize], len: usize) -> bool {
+    spec.iter().fold(spec.len().saturating_sub(1), |acc, &c| acc.saturating_add(c)) <= len
+}
+
+/// Reads a variable-length integer written by `push_varint`
+fn read_varint<I: Iterator<Item=u8>>(bytes: &mut I) -> Result<usize, ParseError> {
+    let mut n: usize = 0;
+    let mut shift = 0;
+    loop {
+        let b = bytes.next().ok_or(ParseError("Unexpected end of URL parameter".to_string()))?;
+        let bits = (b & 0x7f) as usize;
+        if shift >= usize::BITS || bits.checked_shr(usize::BITS - shift).unwrap_or(0) != 0 {
+            return Err(ParseError("Integer too large in URL parameter".to_string()));
+        }
+        n |= bits << shift;
+        if b & 0x80 == 0 {
+            return Ok(n);
+        }
+        shift += 7;
+    }
+}
+
+impl Picross {
+    ///
+    /// Encodes the cells into a compact string, suitable for URL parameters
+    ///
+    /// The string is made of the dimensions, as `HEIGHTxWIDTH:`, followed by the
+    /// cells packed 2 bits per cell, row by row, and encoded as URL-safe base64.
+    /// Hints are encoded as their value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Black, Cell::White, Cell::Unknown],
+    ///                 vec![Cell::Unknown, Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let encoded = picross.encode_cells_as_base64();
+    /// assert!(encoded.starts_with("2x3:"));
+    /// assert_eq!(Picross::decode_cells_from_base64(&encoded, 2, 3), Ok(picross.cells));
+    /// ```
+    ///
+    pub fn encode_cells_as_base64(&self) -> String {
+        let mut bytes = vec![];
+        for (i, c) in self.iter_cells().enumerate() {
+            if i % 4 == 0 {
+                bytes.push(0);
+            }
+            *bytes.last_mut().expect("Byte just pushed") |= cell_code(*c) << (6 - 2 * (i % 4));
+        }
+        format!("{}x{}:{}", self.height, self.length, URL_SAFE_NO_PAD.encode(&bytes))
+    }
+
+    ///
+    /// Decodes cells encoded by `encode_cells_as_base64`, checking they form a
+    /// grid of `height` rows and `width` columns
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::Picross;
+    /// use picross::Cell::{Black, White};
+    /// use picross::error::DecodeError;
+    ///
+    /// assert_eq!(Picross::decode_cells_from_base64("1x2:YA", 1, 2),
+    ///            Ok(vec![vec![Black, White]]));
+    /// assert!(Picross::decode_cells_from_base64("1x2:YA", 2, 1).is_err());
+    /// assert_eq!(Picross::decode_cells_from_base64("YA", 1, 2),
+    ///            Err(DecodeError::MissingDimensions));
+    /// ```
+    ///
+    pub fn decode_cells_from_base64(s: &str, height: usize, width: usize)
+            -> Result<Vec<Vec<Cell>>, DecodeError> {
+        let (dims, data) = s.split_once(':').ok_or(DecodeError::MissingDimensions)?;
+        let (h, w) = dims.split_once('x').ok_or(DecodeError::MissingDimensions)?;
+        let (h, w) = match (h.parse::<usize>(), w.parse::<usize>()) {
+            (Ok(h), Ok(w)) => (h, w),
+            _              => return Err(DecodeError::MissingDimensions),
+        };
+        if h != height || w != width {
+            return Err(DecodeError::DimensionMismatch {
+                expected_height: height,
+                expected_width: width,
+                height: h,
+                width: w,
+            });
+        }
+
+        let bytes = URL_SAFE_NO_PAD.decode(data)
+                                   .map_err(|e| DecodeError::InvalidBase64(e.to_string()))?;
+        let expected = (height * width).div_ceil(4);
+        if bytes.len() != expected {
+            return Err(DecodeError::WrongLength { expected, found: bytes.len() });
+        }
+
+        let cells = (0..height * width).map(|i| {
+            match (bytes[i / 4] >> (6 - 2 * (i % 4))) & 3 {
+                0 => Ok(Cell::Unknown),
+                1 => Ok(Cell::Black),
+                2 => Ok(Cell::White),
+                c => Err(DecodeError::InvalidCell(c)),
+            }
+        }).collect::<Result<Vec<Cell>, DecodeError>>()?;
+
+        Ok(if width == 0 {
+            vec![vec![]; height]
+        } else {
+            cells.chunks(width).map(|r| r.to_vec()).collect()
+        })
+    }
+
+    ///
+    /// Encodes the specifications into a compact string, suitable for sharing
+    /// puzzles as links
+    ///
+    /// The height, the length, then for each row and each column its number of
+    /// clues followed by the clues are written as variable-length integers (7 bits
+    /// per byte, the high bit marking that more bytes follow), and the result is
+    /// encoded as URL-safe base64. The cells are not encoded, see
+    /// `encode_cells_as_base64` for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![1, 1], vec![]],
+    ///     col_spec: vec![vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let param = picross.to_url_param();
+    /// assert_eq!(param, "AgMCAQEAAQEAAQE");
+    ///
+    /// let parsed = Picross::parse_from_url_param(&param).unwrap();
+    /// assert_eq!(parsed.row_spec, picross.row_spec);
+    /// assert_eq!(parsed.col_spec, picross.col_spec);
+    /// ```
+    ///
+    pub fn to_url_param(&self) -> String {
+        let mut bytes = vec![];
+        push_varint(&mut bytes, self.height);
+        push_varint(&mut bytes, self.length);
+        for spec in self.row_spec.iter().chain(self.col_spec.iter()) {
+            push_varint(&mut bytes, spec.len());
+            for &clue in spec {
+                push_varint(&mut bytes, clue);
+            }
+        }
+        URL_SAFE_NO_PAD.encode(&bytes)
+    }
+
+    ///
+    /// Parses specifications encoded by `to_url_param`
+    ///
+    /// The cells are all `Cell::Unknown`. Returns an error if the height or the
+    /// length is over 1000, or if the clues of a line do not fit in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross::parse_from_url_param("AQIBAgEBAQE").unwrap();
+    /// assert_eq!(picross.row_spec, vec![vec![2]]);
+    /// assert_eq!(picross.col_spec, vec![vec![1], vec![1]]);
+    ///
+    /// assert!(Picross::parse_from_url_param("AQIBAgEB").is_err());
+    /// assert!(Picross::parse_from_url_param("not base64!").is_err());
+    ///
+    /// // A row of 2 cells with a clue of 3
+    /// assert!(Picross::parse_from_url_param("AQIBAwEBAQE").is_err());
+    ///
+    /// // A height of 2^64 and a length of 0: the height does not fit in 64 bits
+    /// assert!(Picross::parse_from_url_param("gICAgICAgICAAgA").is_err());
+    ///
+    /// let mut huge = Picross {
+    ///     height: 1001,
+    ///     length: 1,
+    ///     cells: vec![],
+    ///     row_spec: vec![vec![]; 1001],
+    ///     col_spec: vec![vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// assert!(Picross::parse_from_url_param(&huge.to_url_param()).is_err());
+    /// huge.height = 1000;
+    /// huge.row_spec.pop();
+    /// assert!(Picross::parse_from_url_param(&huge.to_url_param()).is_ok());
+    /// ```
+    ///
+    pub fn parse_from_url_param(param: &str) -> Result<Picross, ParseError> {
+        let bytes = URL_SAFE_NO_PAD.decode(param)
+            .map_err(|e| ParseError(format!("Invalid base64 in URL parameter: {}", e)))?;
+        let mut bytes = bytes.into_iter();
+
+        let height = read_varint(&mut bytes)?;
+        let length = read_varint(&mut bytes)?;
+        if height > MAX_URL_DIMENSION || length > MAX_URL_DIMENSION {
+            return Err(ParseError(format!("Dimensions {}x{} too large in URL parameter", height, length)));
+        }
+        let mut specs = vec![];
+        for i in 0..height + length {
+            let len = read_varint(&mut bytes)?;
+            let spec = (0..len).map(|_| read_varint(&mut bytes)).collect::<Result<Vec<usize>, ParseError>>()?;
+            if !spec_fits(&spec, if i < height { length } else { height }) {
+                return Err(ParseError(format!("Clues {:?} do not fit in URL parameter", spec)));
+            }
+            specs.push(spec);
+        }
+        if bytes.next().is_some() {
+            return Err(ParseError("Unexpected trailing data in URL parameter".to_string()));
+        }
+
+        let col_spec = specs.split_off(height);
+        Ok(Picross {
+            height,
+            length,
+            cells: vec![vec![Cell::Unknown; length]; height],
+            row_spec: specs,
+            col_spec,
+            possible_rows: vec![],
+            possible_cols: vec![],
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use std::io;
+
+use thiserror::Error;
+
+use ::ValidationFailure;
+use parse::ParseError;
+use solve::Contradiction;
+
+/// Any error that can happen while handling a Picross grid
+#[derive(Debug, Error)]
+pub enum PicrossError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+    #[error(transparent)]
+    Solve(#[from] SolveError),
+    #[error(transparent)]
+    Fill(#[from] FillError),
+    #[error(transparent)]
+    OutOfBounds(#[from] OutOfBoundsError),
+    #[error(transparent)]
+    IterationLimit(#[from] IterationLimitExceeded),
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl From<Contradiction> for PicrossError {
+    fn from(c: Contradiction) -> PicrossError {
+        PicrossError::Solve(SolveError::from(c))
+    }
+}
+
+/// Error returned when a Picross grid is not valid, with all the reasons why
+#[derive(Clone, PartialEq, Debug, Error)]
+#[error("Invalid grid: {}", failures_to_string(.0))]
+pub struct ValidationError(pub Vec<ValidationFailure>);
+
+/// Error returned when a Picross grid cannot be solved
+#[derive(Clone, Copy, PartialEq, Debug, Error)]
+pub enum SolveError {
+    #[error(transparent)]
+    Contradiction(#[from] Contradiction),
+}
+
+/// Error returned when a cell cannot be filled during an interactive game
+#[derive(Clone, Copy, PartialEq, Debug, Error)]
+pub enum FillError {
+    #[error(transparent)]
+    OutOfBounds(#[from] OutOfBoundsError),
+    #[error("Cell ({row}, {col}) is already filled")]
+    AlreadyFilled { row: usize, col: usize },
+    #[error("Cannot fill a cell with an unknown value")]
+    UnknownValue,
+}
+
+/// Error returned when cells cannot be decoded with
+/// `Picross::decode_cells_from_base64`
+#[derive(Clone, PartialEq, Debug, Error)]
+pub enum DecodeError {
+    #[error("Expected a prefix of form HEIGHTxWIDTH:")]
+    MissingDimensions,
+    #[error("Expected a {expected_height}x{expected_width} grid but found {height}x{width}")]
+    DimensionMismatch { expected_height: usize, expected_width: usize, height: usize, width: usize },
+    #[error("Invalid base64: {0}")]
+    InvalidBase64(String),
+    #[error("Expected {expected} bytes of cells but found {found}")]
+    WrongLength { expected: usize, found: usize },
+    #[error("Invalid cell value {0}")]
+    InvalidCell(u8),
+}
+
+/// Error returned when two partial solutions disagree on a cell
+#[derive(Clone, Copy, PartialEq, Debug, Error)]
+#[error("Cell ({row}, {col}) is black in one solution and white in the other")]
+pub struct MergeConflict {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Error returned when a rectangular region does not fit in the grid
+#[derive(Clone, Copy, PartialEq, Debug, Error)]
+#[error("Region ({r0}, {c0})..({r1}, {c1}) does not fit in a {height}x{length} grid")]
+pub struct OutOfBoundsError {
+    pub r0: usize,
+    pub c0: usize,
+    pub r1: usize,
+    pub c1: usize,
+    pub height: usize,
+    pub length: usize,
+}
+
+/// Error returned when `Picross::solve_with_iteration_limit` needs more passes
+/// over the lines than allowed
+#[derive(Clone, Copy, PartialEq, Debug, Error)]
+#[error("The solver did not finish within {0} passes over the lines")]
+pub struct IterationLimitExceeded(pub usize);
+
+/// Joins the descriptions of `failures`
+fn failures_to_string(failures: &[ValidationFailure]) -> String {
+    failures.iter().map(|f| f.to_string()).collect::<Vec<String>>().join("; ")
+}
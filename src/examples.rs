@@ -0,0 +1,78 @@
+use ::{Cell, Picross};
+
+/// Names and solutions of the bundled examples, `#` standing for black cells and
+/// `.` for white cells
+const EXAMPLES: &[(&str, &str)] = &[
+    ("heart", "\
+.##.##.
+#######
+#######
+.#####.
+..###..
+...#...
+"),
+    ("boat", "\
+...#....
+...##...
+...###..
+...####.
+...#....
+########
+.######.
+..####..
+"),
+    ("diamond", "\
+...#...
+..###..
+.#####.
+#######
+.#####.
+..###..
+...#...
+"),
+    ("cross", "\
+..###..
+..###..
+#######
+#######
+#######
+..###..
+..###..
+"),
+];
+
+impl Picross {
+    ///
+    /// Loads one of the puzzles bundled with the library, or returns `None` if
+    /// there is no example called `name`
+    ///
+    /// The available examples are `heart`, `boat`, `diamond` and `cross`. They all
+    /// have a unique solution, and their cells are all `Cell::Unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::Picross;
+    ///
+    /// let mut heart = Picross::load_bundled_example("heart").unwrap();
+    /// assert_eq!(heart.row_spec[0], vec![2, 2]);
+    /// assert!(heart.auto_solve_and_verify());
+    ///
+    /// for name in &["heart", "boat", "diamond", "cross"] {
+    ///     assert!(Picross::load_bundled_example(name).unwrap().has_unique_solution());
+    /// }
+    ///
+    /// assert!(Picross::load_bundled_example("dragon").is_none());
+    /// ```
+    ///
+    pub fn load_bundled_example(name: &str) -> Option<Picross> {
+        let &(_, image) = EXAMPLES.iter().find(|&&(n, _)| n == name)?;
+        let pixels = image.lines()
+                          .map(|l| l.chars().map(|c| c == '#').collect())
+                          .collect::<Vec<Vec<bool>>>();
+        let mut picross = Picross::from_qr_code_matrix(&pixels, None, None)
+            .expect("Bundled examples are valid");
+        picross.cells = vec![vec![Cell::Unknown; picross.length]; picross.height];
+        Some(picross)
+    }
+}
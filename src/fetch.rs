@@ -0,0 +1,92 @@
+use std::io::Cursor;
+
+use reqwest::header::CONTENT_TYPE;
+use thiserror::Error;
+
+use ::Picross;
+use db::DbFormat;
+use parse::ParseError;
+use stream::PicrossStreamParser;
+
+/// Error returned when `Picross::load_from_url` cannot fetch or parse a puzzle
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("The server answered with status {0}")]
+    Status(u16),
+    #[error("Cannot guess the format of the puzzle at {0}")]
+    UnknownFormat(String),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+///
+/// Guesses the format of a puzzle from the extension of its URL and its
+/// `Content-Type` header
+///
+/// A `.pbn` or `.xml` extension wins, since servers often send these files as
+/// generic text. Otherwise, XML documents are `DbFormat::Pbn`, and plain text and
+/// `.txt` files are `DbFormat::Compact`. Returns `None` if nothing tells.
+///
+/// # Examples
+///
+/// ```
+/// use picross::db::DbFormat;
+/// use picross::fetch::detect_format;
+///
+/// assert_eq!(detect_format(Some("text/xml; charset=utf-8"), "http://a/b"), Some(DbFormat::Pbn));
+/// assert_eq!(detect_format(Some("text/plain"), "http://a/b.pbn"), Some(DbFormat::Pbn));
+/// assert_eq!(detect_format(Some("application/octet-stream"), "http://a/b.PBN?id=3"),
+///            Some(DbFormat::Pbn));
+/// assert_eq!(detect_format(Some("text/plain"), "http://a/b"), Some(DbFormat::Compact));
+/// assert_eq!(detect_format(None, "http://a/b.txt"), Some(DbFormat::Compact));
+/// assert_eq!(detect_format(None, "http://a/b"), None);
+/// ```
+///
+pub fn detect_format(content_type: Option<&str>, url: &str) -> Option<DbFormat> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let file = path.rsplit('/').next().unwrap_or(path);
+    let ext = file.rsplit('.').next().filter(|_| file.contains('.')).map(|e| e.to_lowercase());
+    if let Some("pbn") | Some("xml") = ext.as_deref() {
+        return Some(DbFormat::Pbn);
+    }
+
+    let mime = content_type.and_then(|c| c.split(';').next()).map(|m| m.trim().to_lowercase());
+    match (mime.as_deref(), ext.as_deref()) {
+        (Some("text/xml"), _) | (Some("application/xml"), _) => Some(DbFormat::Pbn),
+        (Some("text/plain"), _) | (_, Some("txt"))           => Some(DbFormat::Compact),
+        _                                                    => None,
+    }
+}
+
+impl Picross {
+    ///
+    /// Fetches the puzzle at `url` over HTTP and parses it
+    ///
+    /// The format is guessed with `detect_format`. PBN documents are parsed with
+    /// `from_pbn`, and compact documents, as written by `to_string_compact`, hold
+    /// the specifications only. Only the first puzzle of the document is returned.
+    ///
+    /// This is only available with the `fetch` feature.
+    ///
+    pub fn load_from_url(url: &str) -> Result<Picross, FetchError> {
+        let response = reqwest::blocking::get(url)?;
+        if !response.status().is_success() {
+            return Err(FetchError::Status(response.status().as_u16()));
+        }
+        let content_type = response.headers().get(CONTENT_TYPE)
+            .and_then(|c| c.to_str().ok())
+            .map(|c| c.to_string());
+        let body = response.text()?;
+
+        match detect_format(content_type.as_deref(), url) {
+            Some(DbFormat::Pbn)     => Ok(Picross::from_pbn(&body)?),
+            Some(DbFormat::Compact) => {
+                let first = PicrossStreamParser::new(Cursor::new(body)).next();
+                Ok(first.unwrap_or_else(|| Err(ParseError("Expected a puzzle".to_string())))?)
+            }
+            None => Err(FetchError::UnknownFormat(url.to_string())),
+        }
+    }
+}
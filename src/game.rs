@@ -0,0 +1,100 @@
+use ::{Cell, Direction, Picross};
+use error::{FillError, OutOfBoundsError};
+
+/// Consequences of filling a cell with `Picross::interactive_fill`
+#[derive(Clone, PartialEq, Debug)]
+pub struct FillEffect {
+    /// Cells set by the call, as `(row, col, value)`, starting with the filled one
+    pub cells_changed: Vec<(usize, usize, Cell)>,
+    /// True if the row or the column of the filled cell can no longer match its
+    /// specification
+    pub is_contradiction: bool,
+    /// True if the grid is now completely and correctly filled
+    pub is_complete: bool,
+}
+
+impl Picross {
+    ///
+    /// Fills cell `(row, col)` with `cell`, then applies the line solver on its row
+    /// and its column
+    ///
+    /// This bundles the "fill a cell, propagate, check whether the game is over"
+    /// loop of interactive games. If a contradiction is found, the cells set before
+    /// it are kept and reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    /// use picross::error::{FillError, OutOfBoundsError};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let effect = picross.interactive_fill(0, 0, Black).unwrap();
+    /// assert_eq!(effect.cells_changed, vec![(0, 0, Black), (0, 1, White), (1, 0, White)]);
+    /// assert!(!effect.is_contradiction);
+    /// assert!(!effect.is_complete);
+    ///
+    /// let effect = picross.interactive_fill(1, 1, Black).unwrap();
+    /// assert!(effect.is_complete);
+    ///
+    /// assert_eq!(picross.interactive_fill(1, 1, White),
+    ///            Err(FillError::AlreadyFilled { row: 1, col: 1 }));
+    /// assert_eq!(picross.interactive_fill(2, 0, White),
+    ///            Err(FillError::OutOfBounds(OutOfBoundsError {
+    ///                r0: 2, c0: 0, r1: 3, c1: 1, height: 2, length: 2,
+    ///            })));
+    /// ```
+    ///
+    pub fn interactive_fill(&mut self, row: usize, col: usize, cell: Cell)
+            -> Result<FillEffect, FillError> {
+        if row >= self.height || col >= self.length {
+            return Err(FillError::OutOfBounds(OutOfBoundsError {
+                r0: row,
+                c0: col,
+                r1: row.saturating_add(1),
+                c1: col.saturating_add(1),
+                height: self.height,
+                length: self.length,
+            }));
+        }
+        if self.cells[row][col] != Cell::Unknown {
+            return Err(FillError::AlreadyFilled { row, col });
+        }
+        if cell == Cell::Unknown {
+            return Err(FillError::UnknownValue);
+        }
+
+        self.cells[row][col] = cell;
+        let mut cells_changed = vec![(row, col, cell)];
+        let mut is_contradiction = false;
+
+        for &(dir, idx) in &[(Direction::Row, row), (Direction::Col, col)] {
+            match self.apply_line_constraint(dir, idx) {
+                Ok(changes) => cells_changed.extend(changes.into_iter().map(|(i, c)| match dir {
+                    Direction::Row => (idx, i, c),
+                    Direction::Col => (i, idx, c),
+                })),
+                Err(_) => {
+                    is_contradiction = true;
+                    break;
+                }
+            }
+        }
+
+        Ok(FillEffect {
+            cells_changed,
+            is_contradiction,
+            is_complete: self.is_valid(),
+        })
+    }
+}
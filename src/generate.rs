@@ -0,0 +1,330 @@
+#[cfg(feature = "image")]
+use std::path::Path;
+
+#[cfg(feature = "image")]
+use image;
+use rand::Rng;
+
+use ::{Cell, Picross, ValidationFailure};
+use error::ValidationError;
+use solve::{Difficulty, SolveResult};
+
+/// Builds the solved Picross grid whose cells are `cells`, deriving the
+/// specifications from them
+fn solved_from_cells(cells: Vec<Vec<Cell>>) -> Picross {
+    let mut picross = Picross {
+        height: cells.len(),
+        length: cells.first().map(|r| r.len()).unwrap_or(0),
+        cells,
+        row_spec: vec![],
+        col_spec: vec![],
+        possible_rows: vec![],
+        possible_cols: vec![],
+    };
+    picross.row_spec = picross.cells.iter().map(|r| Picross::cells_to_runs(r)).collect();
+    picross.col_spec = picross.transpose().iter().map(|c| Picross::cells_to_runs(c)).collect();
+    picross
+}
+
+/// Builds an unsolved Picross grid from a random board where each cell is black
+/// with probability `density`
+fn random_puzzle<R: Rng>(height: usize, length: usize, density: f64, rng: &mut R) -> Picross {
+    let cells = (0..height).map(|_| {
+        (0..length).map(|_| if rng.gen_bool(density) { Cell::Black } else { Cell::White })
+                   .collect::<Vec<Cell>>()
+    }).collect::<Vec<Vec<Cell>>>();
+
+    let mut picross = solved_from_cells(cells);
+    picross.cells = vec![vec![Cell::Unknown; length]; height];
+    picross
+}
+
+impl Picross {
+    ///
+    /// Generates a random Picross grid of `height` rows and `length` columns that
+    /// has a unique solution
+    ///
+    /// Each try draws a random board where each cell is black with probability
+    /// `density`, derives the specifications from it and checks the solution is
+    /// unique. A `density` of 0.5 is a sensible default, but it strongly impacts
+    /// the chances of finding a puzzle with a unique solution.
+    ///
+    /// Returns `None` if no such grid was found in `max_retries` tries. The cells of
+    /// the returned grid are all `Cell::Unknown`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `density` is not between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rand;
+    /// extern crate picross;
+    ///
+    /// use picross::Picross;
+    ///
+    /// # fn main() {
+    /// let mut rng = rand::thread_rng();
+    ///
+    /// // A completely black board is always uniquely solvable
+    /// let picross = Picross::generate_unique_random(3, 4, 1., &mut rng, 1).unwrap();
+    /// assert_eq!(picross.row_spec, vec![vec![4], vec![4], vec![4]]);
+    ///
+    /// if let Some(mut picross) = Picross::generate_unique_random(5, 5, 0.5, &mut rng, 100) {
+    ///     assert!(picross.has_unique_solution());
+    ///     picross.propagate().unwrap();
+    /// }
+    /// # }
+    /// ```
+    ///
+    pub fn generate_unique_random<R: Rng>(height: usize, length: usize, density: f64,
+                                          rng: &mut R, max_retries: usize) -> Option<Picross> {
+        for _ in 0..max_retries {
+            let picross = random_puzzle(height, length, density, rng);
+            if picross.has_unique_solution() {
+                return Some(picross);
+            }
+        }
+        None
+    }
+
+    ///
+    /// Generates a random Picross grid of `height` rows and `length` columns that
+    /// can be solved by line solving alone, without any guess
+    ///
+    /// Each try draws a random board where half the cells are black, derives the
+    /// specifications from it and runs the line solver on it. Such a grid always
+    /// has a unique solution, but not all grids with a unique solution can be found
+    /// this way, unlike with `generate_unique_random`.
+    ///
+    /// Returns `None` if no such grid was found in `max_retries` tries. The cells of
+    /// the returned grid are all `Cell::Unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rand;
+    /// extern crate picross;
+    ///
+    /// use picross::Picross;
+    /// use picross::solve::{Difficulty, SolveResult};
+    ///
+    /// # fn main() {
+    /// let mut rng = rand::thread_rng();
+    ///
+    /// if let Some(mut picross) = Picross::generate_easy_puzzle(5, 5, &mut rng, 100) {
+    ///     assert_eq!(picross.solve_with_difficulty_bound(Difficulty::Easy), SolveResult::Solved);
+    /// }
+    /// # }
+    /// ```
+    ///
+    pub fn generate_easy_puzzle<R: Rng>(height: usize, length: usize,
+                                        rng: &mut R, max_retries: usize) -> Option<Picross> {
+        for _ in 0..max_retries {
+            let picross = random_puzzle(height, length, 0.5, rng);
+            if picross.clone().solve_with_difficulty_bound(Difficulty::Easy) == SolveResult::Solved {
+                return Some(picross);
+            }
+        }
+        None
+    }
+
+    ///
+    /// Generates a solved Picross grid from grayscale pixels
+    ///
+    /// `pixels` holds the rows of the image one after the other, each row being
+    /// `width` pixels long. Pixels darker than `threshold` become black cells, the
+    /// others becoming white cells. The specifications are derived from these
+    /// cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is 0 or does not divide the number of pixels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::Picross;
+    ///
+    /// let pixels = [  0, 200,  10,
+    ///               255,  90, 255];
+    ///
+    /// let picross = Picross::from_image_grid(&pixels, 3, 100);
+    /// assert_eq!(picross.height, 2);
+    /// assert_eq!(picross.row_spec, vec![vec![1, 1], vec![1]]);
+    /// assert_eq!(picross.col_spec, vec![vec![1], vec![1], vec![1]]);
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    pub fn from_image_grid(pixels: &[u8], width: usize, threshold: u8) -> Picross {
+        if pixels.len().checked_rem(width) != Some(0) {
+            panic!("Expected rows of {} pixels, but got {} pixels!", width, pixels.len());
+        }
+
+        let cells = pixels.chunks(width).map(|row| {
+            row.iter().map(|&p| if p < threshold { Cell::Black } else { Cell::White })
+               .collect::<Vec<Cell>>()
+        }).collect::<Vec<Vec<Cell>>>();
+        solved_from_cells(cells)
+    }
+
+    ///
+    /// Converts the cells into a boolean matrix, `true` standing for black cells
+    ///
+    /// A QR code is a square matrix of dark and light modules, that is exactly a
+    /// solved Picross grid: the matrix produced by a QR code library can be turned
+    /// into a puzzle with `from_image_grid`, and a solved puzzle can be fed back to
+    /// such a library with this method. Unknown cells are treated as white.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Unknown, Cell::Hint(true)]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.to_qr_code_matrix(), vec![vec![true, false], vec![false, true]]);
+    /// ```
+    ///
+    pub fn to_qr_code_matrix(&self) -> Vec<Vec<bool>> {
+        self.cells.iter()
+            .map(|row| row.iter().map(|c| c.value() == Cell::Black).collect())
+            .collect()
+    }
+
+    ///
+    /// Builds a solved Picross grid from a boolean matrix, `true` standing for black
+    /// cells
+    ///
+    /// This is the inverse of `to_qr_code_matrix`. The specifications that are
+    /// `None` are derived from the matrix, and the resulting grid is then checked
+    /// against the others, returning all the failures if it does not match them.
+    /// A matrix whose rows do not all have the same length is a
+    /// `ValidationFailure::DimensionMismatch`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, ValidationFailure};
+    ///
+    /// let pixels = vec![vec![true, false], vec![true, true]];
+    ///
+    /// let picross = Picross::from_qr_code_matrix(&pixels, None, None).unwrap();
+    /// assert_eq!(picross.row_spec, vec![vec![1], vec![2]]);
+    /// assert_eq!(picross.col_spec, vec![vec![2], vec![1]]);
+    /// assert_eq!(picross.cells[0], vec![Cell::Black, Cell::White]);
+    /// assert_eq!(picross.to_qr_code_matrix(), pixels);
+    ///
+    /// let err = Picross::from_qr_code_matrix(&pixels, Some(vec![vec![1], vec![1]]), None)
+    ///     .unwrap_err();
+    /// assert_eq!(err.0, vec![
+    ///     ValidationFailure::RowSpecNotMatched { row: 1, spec: vec![1], found: vec![2] },
+    /// ]);
+    ///
+    /// let ragged = vec![vec![true, false], vec![true]];
+    /// let err = Picross::from_qr_code_matrix(&ragged, None, None).unwrap_err();
+    /// assert_eq!(err.0, vec![ValidationFailure::DimensionMismatch]);
+    /// ```
+    ///
+    pub fn from_qr_code_matrix(pixels: &[Vec<bool>],
+                               row_spec: Option<Vec<Vec<usize>>>,
+                               col_spec: Option<Vec<Vec<usize>>>)
+            -> Result<Picross, ValidationError> {
+        if pixels.iter().any(|row| row.len() != pixels[0].len()) {
+            return Err(ValidationError(vec![ValidationFailure::DimensionMismatch]));
+        }
+        let cells = pixels.iter().map(|row| {
+            row.iter().map(|&p| if p { Cell::Black } else { Cell::White }).collect()
+        }).collect();
+        let mut picross = solved_from_cells(cells);
+        if let Some(spec) = row_spec {
+            picross.row_spec = spec;
+        }
+        if let Some(spec) = col_spec {
+            picross.col_spec = spec;
+        }
+        picross.validate()?;
+        Ok(picross)
+    }
+
+    ///
+    /// Returns the solution as a boolean matrix, `true` standing for black cells, or
+    /// `None` if the grid is not completely and correctly filled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 1,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::Unknown]],
+    ///     row_spec: vec![vec![1]],
+    ///     col_spec: vec![vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// assert_eq!(picross.solution_grid(), None);
+    ///
+    /// picross.cells[0][1] = Cell::White;
+    /// assert_eq!(picross.solution_grid(), Some(vec![vec![true, false]]));
+    /// ```
+    ///
+    pub fn solution_grid(&self) -> Option<Vec<Vec<bool>>> {
+        if self.is_valid() {
+            Some(self.to_qr_code_matrix())
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Generates a solved Picross grid from the image at `path`
+    ///
+    /// The image is converted to grayscale, and each pixel darker than mid-gray
+    /// becomes a black cell, the others becoming white cells. The specifications are
+    /// derived from these cells. PNG and JPEG images are supported.
+    ///
+    /// Only available with the `image` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate image;
+    /// extern crate picross;
+    ///
+    /// use picross::Picross;
+    ///
+    /// # fn main() {
+    /// let path = std::env::temp_dir().join("picross_generate_from_image.png");
+    /// let img = image::GrayImage::from_fn(3, 2, |x, y| {
+    ///     image::Luma([if x == y { 0 } else { 255 }])
+    /// });
+    /// img.save(&path).unwrap();
+    ///
+    /// let picross = Picross::generate_from_image(&path).unwrap();
+    /// assert_eq!(picross.height, 2);
+    /// assert_eq!(picross.length, 3);
+    /// assert_eq!(picross.row_spec, vec![vec![1], vec![1]]);
+    /// assert_eq!(picross.col_spec, vec![vec![1], vec![1], vec![]]);
+    /// assert!(picross.is_valid());
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "image")]
+    pub fn generate_from_image(path: &Path) -> Result<Picross, image::ImageError> {
+        let img = image::open(path)?.to_luma8();
+        Ok(Picross::from_image_grid(img.as_raw(), img.width() as usize, 128))
+    }
+}
@@ -0,0 +1,76 @@
+use ::{Direction, Picross};
+
+/// Dependencies between the lines of a grid during line solving, as returned by
+/// `Picross::compute_propagation_graph`
+#[derive(Clone, PartialEq, Debug)]
+pub struct PropagationGraph {
+    /// All the lines of the grid, rows first
+    pub nodes: Vec<(Direction, usize)>,
+    /// `(a, b)` is an edge if solving line `a` determined a cell of line `b`, in
+    /// the order the edges were first found
+    pub edges: Vec<((Direction, usize), (Direction, usize))>,
+}
+
+impl PropagationGraph {
+    ///
+    /// Returns the lines that received cells determined by solving `line`
+    ///
+    /// See `Picross::compute_propagation_graph` for an example.
+    ///
+    pub fn successors(&self, line: (Direction, usize)) -> Vec<(Direction, usize)> {
+        self.edges.iter().filter(|e| e.0 == line).map(|e| e.1).collect()
+    }
+}
+
+impl Picross {
+    ///
+    /// Applies line solving to a copy of the grid, and records which lines helped
+    /// solving which other lines
+    ///
+    /// Line solving stops at the first contradiction, in which case the graph
+    /// only holds the dependencies found until then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![1]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let graph = picross.compute_propagation_graph();
+    /// assert_eq!(graph.nodes.len(), 4);
+    /// assert_eq!(graph.successors((Direction::Row, 0)),
+    ///            vec![(Direction::Col, 0), (Direction::Col, 1)]);
+    /// assert_eq!(graph.successors((Direction::Col, 0)), vec![(Direction::Row, 1)]);
+    /// ```
+    ///
+    pub fn compute_propagation_graph(&self) -> PropagationGraph {
+        let nodes = (0..self.height).map(|y| (Direction::Row, y))
+            .chain((0..self.length).map(|x| (Direction::Col, x)))
+            .collect();
+
+        let mut edges = vec![];
+        let _ = self.clone().propagate_observed(&mut |_, (dir, idx), changes| {
+            let cross = match dir {
+                Direction::Row => Direction::Col,
+                Direction::Col => Direction::Row,
+            };
+            for &(i, _) in changes {
+                let edge = ((dir, idx), (cross, i));
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+        });
+
+        PropagationGraph { nodes, edges }
+    }
+}
@@ -0,0 +1,124 @@
+use ::{spec_to_string, Picross};
+
+/// Hashes `data` with the 64-bit FNV-1a algorithm
+fn fnv1a(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in data {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+impl Picross {
+    ///
+    /// Returns a hash of the specifications of the puzzle, ignoring the cells
+    ///
+    /// The hash is computed with FNV-1a over `to_string_compact`, so it is stable
+    /// across runs and platforms and can be used to deduplicate puzzles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]],
+    ///     row_spec: vec![vec![1]],
+    ///     col_spec: vec![vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.hash_puzzle(), 0x7d65_bb1b_ba1b_bc42);
+    ///
+    /// let mut solved = picross.clone();
+    /// solved.cells = vec![vec![Cell::Black, Cell::White]];
+    /// assert_eq!(picross.hash_puzzle(), solved.hash_puzzle());
+    ///
+    /// let mut other = picross.clone();
+    /// other.col_spec = vec![vec![], vec![1]];
+    /// assert!(picross.hash_puzzle() != other.hash_puzzle());
+    /// ```
+    ///
+    pub fn hash_puzzle(&self) -> u64 {
+        fnv1a(self.to_string_compact().as_bytes())
+    }
+
+    ///
+    /// Returns a hash of the specifications and of the cells of the puzzle
+    ///
+    /// Like `hash_puzzle`, the hash is stable across runs and platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]],
+    ///     row_spec: vec![vec![1]],
+    ///     col_spec: vec![vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let mut solved = picross.clone();
+    /// solved.cells = vec![vec![Cell::Black, Cell::White]];
+    /// assert!(picross.hash_state() != solved.hash_state());
+    /// assert_eq!(picross.hash_state(), picross.clone().hash_state());
+    /// ```
+    ///
+    pub fn hash_state(&self) -> u64 {
+        let mut data = self.to_string_compact();
+        for row in &self.cells {
+            data.push('\n');
+            data.push_str(&Picross::cells_to_string(row));
+        }
+        fnv1a(data.as_bytes())
+    }
+
+    ///
+    /// Returns a hash of the specification of row `row`
+    ///
+    /// The hash only depends on the clues, so it is stable across runs and
+    /// platforms, and a row and a column with the same clues have the same hash.
+    /// Combined with the current cells of the line, it can be used as a key to
+    /// memoize line solving.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_spec_hash(0), picross.col_spec_hash(1));
+    /// assert!(picross.row_spec_hash(0) != picross.row_spec_hash(1));
+    /// ```
+    ///
+    pub fn row_spec_hash(&self, row: usize) -> u64 {
+        fnv1a(spec_to_string(&self.row_spec[row]).as_bytes())
+    }
+
+    ///
+    /// Returns a hash of the specification of column `col`
+    ///
+    /// See `row_spec_hash` for details.
+    ///
+    pub fn col_spec_hash(&self, col: usize) -> u64 {
+        fnv1a(spec_to_string(&self.col_spec[col]).as_bytes())
+    }
+}
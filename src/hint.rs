@@ -0,0 +1,209 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use ::{Cell, Picross};
+
+/// Number of candidate cells compared when choosing each hint of
+/// `generate_hint_puzzle`
+const HINT_CANDIDATES: usize = 8;
+
+/// Returns the number of known cells once line solving is applied to `picross`
+fn known_after_propagation(picross: &Picross) -> usize {
+    let mut picross = picross.clone();
+    let _ = picross.propagate();
+    picross.iter_cells().filter(|c| **c != Cell::Unknown).count()
+}
+
+impl Picross {
+    ///
+    /// Reveals the solution on a partially-filled board
+    ///
+    /// Returns a copy of `self` where each `Cell::Unknown` is replaced by the
+    /// corresponding cell of `solution`, marked as a `Cell::Hint`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `solution` do not have the same dimensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let solution = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let mut board = solution.clone();
+    /// board.cells = vec![vec![Cell::Black  , Cell::Unknown],
+    ///                    vec![Cell::Unknown, Cell::Unknown]];
+    ///
+    /// let revealed = board.annotate_with_solution(&solution);
+    /// assert_eq!(revealed.cells, vec![vec![Cell::Black     , Cell::Hint(false)],
+    ///                                 vec![Cell::Hint(true), Cell::Hint(true) ]]);
+    /// assert!(revealed.is_valid());
+    /// ```
+    ///
+    pub fn annotate_with_solution(&self, solution: &Picross) -> Picross {
+        if self.height != solution.height || self.length != solution.length {
+            panic!("Cannot annotate a board with a solution of different dimensions!");
+        }
+
+        let mut res = self.clone();
+        for (row, sol_row) in res.cells.iter_mut().zip(solution.cells.iter()) {
+            for (c, s) in row.iter_mut().zip(sol_row.iter()) {
+                if *c == Cell::Unknown {
+                    *c = match s.value() {
+                        Cell::Black => Cell::Hint(true),
+                        Cell::White => Cell::Hint(false),
+                        v           => v,
+                    };
+                }
+            }
+        }
+        res
+    }
+
+    ///
+    /// Sets the cells selected by `mask` to their value in `solution`, leaving the
+    /// other cells untouched
+    ///
+    /// `mask[row][col]` is true for the cells to fill. This builds a partially
+    /// filled starting position, whose difficulty depends on the density of the
+    /// mask.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self`, `solution` and `mask` do not have the same dimensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let solution = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let mut board = solution.clone();
+    /// board.cells = vec![vec![Cell::Unknown; 2]; 2];
+    /// board.apply_solution_mask(&solution, &vec![vec![false, true], vec![true, false]]);
+    /// assert_eq!(board.cells, vec![vec![Cell::Unknown, Cell::White],
+    ///                              vec![Cell::Black  , Cell::Unknown]]);
+    /// ```
+    ///
+    pub fn apply_solution_mask(&mut self, solution: &Picross, mask: &[Vec<bool>]) {
+        if self.height != solution.height || self.length != solution.length
+                || mask.len() != self.height || mask.iter().any(|r| r.len() != self.length) {
+            panic!("Cannot apply a solution or a mask of different dimensions!");
+        }
+
+        for (y, mask_row) in mask.iter().enumerate() {
+            for (x, &m) in mask_row.iter().enumerate() {
+                if m {
+                    self.cells[y][x] = solution.cells[y][x].value();
+                }
+            }
+        }
+    }
+
+    ///
+    /// Builds a puzzle from `solution` where a fraction `hint_fraction` of the cells
+    /// is revealed as `Cell::Hint`, the other cells being unknown
+    ///
+    /// The hints are chosen one by one so as to be as useful as possible: each time,
+    /// a few random cells that line solving cannot yet deduce are compared, and the
+    /// one letting line solving determine the most cells is revealed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `solution` is not valid, or if `hint_fraction` is not between 0
+    /// and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rand;
+    /// extern crate picross;
+    ///
+    /// use picross::{Picross, Cell};
+    ///
+    /// # fn main() {
+    /// let solution = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::White, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let puzzle = Picross::generate_hint_puzzle(&solution, 0.25, &mut rand::thread_rng());
+    /// assert_eq!(puzzle.iter_cells().filter(|c| **c != Cell::Unknown).count(), 1);
+    ///
+    /// // A single hint is enough to solve this puzzle
+    /// let mut solved = puzzle.clone();
+    /// solved.propagate().unwrap();
+    /// assert!(solved.is_valid());
+    /// # }
+    /// ```
+    ///
+    pub fn generate_hint_puzzle<R: Rng>(solution: &Picross, hint_fraction: f64, rng: &mut R) -> Picross {
+        if !(0. ..=1.).contains(&hint_fraction) {
+            panic!("Expected a hint fraction between 0 and 1, but got {}!", hint_fraction);
+        }
+        if !solution.is_valid() {
+            panic!("Cannot generate hints from an invalid solution!");
+        }
+
+        let mut res = solution.clone();
+        res.cells = vec![vec![Cell::Unknown; solution.length]; solution.height];
+        let hint_count = (hint_fraction * (solution.height * solution.length) as f64).round() as usize;
+
+        for _ in 0..hint_count {
+            let mut deduced = res.clone();
+            let _ = deduced.propagate();
+            let unknown = |p: &Picross| {
+                (0..p.height).flat_map(|y| (0..p.length).map(move |x| (y, x)))
+                             .filter(|&(y, x)| p.cells[y][x] == Cell::Unknown)
+                             .collect::<Vec<(usize, usize)>>()
+            };
+
+            // Prefer the cells line solving cannot deduce, as revealing the other
+            // ones does not help
+            let mut candidates = unknown(&deduced);
+            if candidates.is_empty() {
+                candidates = unknown(&res);
+            }
+            let reveal = |p: &Picross, (y, x): (usize, usize)| {
+                let mut p = p.clone();
+                p.cells[y][x] = Cell::Hint(solution.cells[y][x].value() == Cell::Black);
+                p
+            };
+            let best = candidates.choose_multiple(rng, HINT_CANDIDATES)
+                                 .max_by_key(|&&pos| known_after_propagation(&reveal(&res, pos)))
+                                 .cloned();
+            match best {
+                Some(pos) => res = reveal(&res, pos),
+                None      => break,
+            }
+        }
+        res
+    }
+}
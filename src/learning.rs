@@ -0,0 +1,133 @@
+use ::{Cell, Picross};
+use solve::{Contradiction, SearchHooks, SolveResult};
+
+/// A set of cell values that no solution of the grid can hold all at once
+struct NoGood(Vec<(usize, usize, Cell)>);
+
+impl NoGood {
+    /// Returns true if `picross` holds all the cell values of the no-good
+    fn matches(&self, picross: &Picross) -> bool {
+        self.0.iter().all(|&(y, x, c)| picross.cells[y][x].value() == c)
+    }
+}
+
+/// Returns true if setting the cell values of `lits` on `root` leads line solving
+/// to a contradiction
+fn refuted_by_propagation(root: &Picross, lits: &[(usize, usize, Cell)]) -> bool {
+    let mut picross = root.clone();
+    for &(y, x, c) in lits {
+        match picross.cells[y][x].value() {
+            Cell::Unknown => picross.cells[y][x] = c,
+            v if v != c   => return true,
+            _             => {}
+        }
+    }
+    picross.propagate().is_err()
+}
+
+/// Learns a no-good from `decisions`, a set of guesses known not to lead to any
+/// solution of `root`
+///
+/// If line solving alone refutes the guesses, guesses are dropped one by one as
+/// long as the remaining ones are still refuted, so that the no-good prunes as
+/// many other branches as possible.
+fn learn(root: &Picross, decisions: &[(usize, usize, Cell)]) -> NoGood {
+    let mut lits = decisions.to_vec();
+    if refuted_by_propagation(root, &lits) {
+        let mut i = 0;
+        while i < lits.len() {
+            let mut smaller = lits.clone();
+            smaller.remove(i);
+            if refuted_by_propagation(root, &smaller) {
+                lits = smaller;
+            } else {
+                i += 1;
+            }
+        }
+    }
+    NoGood(lits)
+}
+
+/// Hooks of `Picross::solve_with_learning`, pruning the branches that match a
+/// learned no-good and learning a new one on each failed guess
+struct LearningHooks<'a> {
+    root: &'a Picross,
+    /// Guesses leading from `root` to the current node
+    decisions: Vec<(usize, usize, Cell)>,
+    nogoods: Vec<NoGood>,
+}
+
+impl<'a> SearchHooks for LearningHooks<'a> {
+    fn propagate(&mut self, picross: &mut Picross) -> Result<(), Contradiction> {
+        picross.propagate()?;
+        if self.nogoods.iter().any(|n| n.matches(picross)) {
+            return Err(Contradiction);
+        }
+        Ok(())
+    }
+
+    fn guess(&mut self, _guess: &Picross, y: usize, x: usize, c: Cell) -> bool {
+        self.decisions.push((y, x, c));
+        true
+    }
+
+    fn backtrack(&mut self, _y: usize, _x: usize, _c: Cell) {
+        self.nogoods.push(learn(self.root, &self.decisions));
+        self.decisions.pop();
+    }
+}
+
+impl Picross {
+
+    ///
+    /// Solves the grid like `solve`, learning from the contradictions found while
+    /// backtracking
+    ///
+    /// This solver is inspired by Conflict-Driven Clause Learning. Each time a
+    /// guess fails, the guesses leading to it are analyzed: those that line
+    /// solving does not need to reach the contradiction are dropped, and the
+    /// remaining ones are stored as a "no-good". Any later branch holding all the
+    /// values of a no-good, even when reached through other guesses, is pruned
+    /// without being explored.
+    ///
+    /// Analyzing conflicts has a cost, which is only worth paying on hard puzzles
+    /// requiring many guesses.
+    ///
+    /// The cells are left untouched if the grid is `SolveResult::Unsolvable`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::solve::SolveResult;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 3,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 3],
+    ///     row_spec: vec![vec![1], vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.solve_with_learning(), SolveResult::Solved);
+    /// assert!(picross.is_valid());
+    ///
+    /// picross.cells = vec![vec![Cell::Unknown; 3]; 3];
+    /// picross.col_spec = vec![vec![1], vec![1], vec![2]];
+    /// assert_eq!(picross.solve_with_learning(), SolveResult::Unsolvable);
+    /// assert_eq!(picross.cells, vec![vec![Cell::Unknown; 3]; 3]);
+    /// ```
+    ///
+    pub fn solve_with_learning(&mut self) -> SolveResult {
+        let root = self.clone();
+        let mut picross = self.clone();
+        if picross.backtrack(&mut LearningHooks { root: &root, decisions: vec![], nogoods: vec![] }) {
+            *self = picross;
+            SolveResult::Solved
+        } else {
+            SolveResult::Unsolvable
+        }
+    }
+}
@@ -1,6 +1,40 @@
+extern crate base64;
+#[cfg(feature = "image")]
+extern crate image;
+extern crate quick_xml;
+extern crate rand;
+#[cfg(feature = "fetch")]
+extern crate reqwest;
+extern crate thiserror;
+
 pub mod display;
 pub mod parse;
 pub mod cache;
+pub mod analysis;
+pub mod solve;
+pub mod generate;
+pub mod hint;
+pub mod pbn;
+pub mod stream;
+pub mod hash;
+pub mod error;
+pub mod tricolor;
+pub mod game;
+pub mod encode;
+pub mod symmetry;
+pub mod learning;
+pub mod origin;
+pub mod graph;
+pub mod non;
+pub mod db;
+pub mod examples;
+pub mod profile;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+
+use std::fmt;
+
+use error::{OutOfBoundsError, ValidationError};
 
 /// The Cell type
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -8,6 +42,107 @@ pub enum Cell {
     Unknown,
     Black,
     White,
+    /// Cell filled by the hint system: `Hint(true)` is black, `Hint(false)` is white
+    Hint(bool),
+}
+
+impl Cell {
+    ///
+    /// Returns the value of the cell, hints being replaced by the value they reveal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::Cell;
+    ///
+    /// assert_eq!(Cell::Hint(true).value(), Cell::Black);
+    /// assert_eq!(Cell::Hint(false).value(), Cell::White);
+    /// assert_eq!(Cell::Unknown.value(), Cell::Unknown);
+    /// ```
+    ///
+    pub fn value(self) -> Cell {
+        match self {
+            Cell::Hint(true)  => Cell::Black,
+            Cell::Hint(false) => Cell::White,
+            c                 => c,
+        }
+    }
+}
+
+/// Returns the blocks of black cells of `line` as `(start, length)` pairs, unknown
+/// cells being treated as white
+pub(crate) fn line_blocks(line: &[Cell]) -> Vec<(usize, usize)> {
+    let mut blocks = vec![];
+    let mut size_block = 0;
+    for (i, c) in line.iter().enumerate() {
+        if c.value() == Cell::Black {
+            size_block += 1;
+        } else if size_block > 0 {
+            blocks.push((i - size_block, size_block));
+            size_block = 0;
+        }
+    }
+    if size_block > 0 {
+        blocks.push((line.len() - size_block, size_block));
+    }
+    blocks
+}
+
+/// Returns the sizes of the blocks of black cells of `line`, unknown cells being
+/// treated as white
+pub(crate) fn line_runs(line: &[Cell]) -> Vec<usize> {
+    line_blocks(line).into_iter().map(|(_, len)| len).collect()
+}
+
+/// Formats `spec` as [1,2,4...]
+fn spec_to_string(spec: &[usize]) -> String {
+    format!("[{}]", spec.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(","))
+}
+
+/// A reason for a Picross grid not to be valid
+#[derive(Clone, PartialEq, Debug)]
+pub enum ValidationFailure {
+    /// `height`, `length`, the cells and the specifications disagree on the dimensions
+    DimensionMismatch,
+    /// The blocks of row `row` are `found` instead of `spec`
+    RowSpecNotMatched { row: usize, spec: Vec<usize>, found: Vec<usize> },
+    /// The blocks of column `col` are `found` instead of `spec`
+    ColSpecNotMatched { col: usize, spec: Vec<usize>, found: Vec<usize> },
+    /// The cell at row `row` and column `col` is `Cell::Unknown`
+    UnknownCell { row: usize, col: usize },
+}
+
+impl fmt::Display for ValidationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationFailure::DimensionMismatch =>
+                write!(f, "Dimensions mismatch between height, length, cells and specs"),
+            ValidationFailure::RowSpecNotMatched { row, ref spec, ref found } =>
+                write!(f, "Row {} failed: spec {} but found {}",
+                       row, spec_to_string(spec), spec_to_string(found)),
+            ValidationFailure::ColSpecNotMatched { col, ref spec, ref found } =>
+                write!(f, "Column {} failed: spec {} but found {}",
+                       col, spec_to_string(spec), spec_to_string(found)),
+            ValidationFailure::UnknownCell { row, col } =>
+                write!(f, "Cell at row {}, column {} is unknown", row, col),
+        }
+    }
+}
+
+/// Whether a line of the board is a row or a column
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    Row,
+    Col,
+}
+
+/// Information about a puzzle that is not needed to solve it
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct PuzzleMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub copyright: Option<String>,
+    pub description: Option<String>,
 }
 
 /// A Picross board
@@ -144,8 +279,39 @@ pub struct Picross {
 /// - parse
 /// - to_string
 /// - is_valid
+/// - side_by_side
+/// - cells_to_runs
+/// - get_line, set_line and line_spec
+/// - iter_cells and iter_cells_mut
+/// - cell_neighbors
+/// - is_rectangular_region_all_black, is_region_all_white, is_region_filled and
+///   set_region_cells
+/// - is_solved
+/// - validate_completeness_against_spec
+/// - assert_valid, diagnose_invalid and validate
 ///
 impl Picross {
+    ///
+    /// Returns the clues matching the line of cells `cells`, that is the sizes of
+    /// its blocks of black cells
+    ///
+    /// The line is meant to be complete: unknown cells are treated as white, and
+    /// hints as the value they reveal. An all-white line has no clue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::Picross;
+    /// use picross::Cell::{Black, White};
+    ///
+    /// assert_eq!(Picross::cells_to_runs(&[Black, Black, White, Black]), vec![2, 1]);
+    /// assert_eq!(Picross::cells_to_runs(&[White, White]), Vec::<usize>::new());
+    /// ```
+    ///
+    pub fn cells_to_runs(cells: &[Cell]) -> Vec<usize> {
+        line_runs(cells)
+    }
+
     ///
     /// Sets row `row` to values `vals`
     ///
@@ -206,6 +372,316 @@ impl Picross {
         }
     }
 
+    ///
+    /// Returns the cells of row or column `idx`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.get_line(Direction::Row, 0), vec![Cell::Black, Cell::White]);
+    /// assert_eq!(picross.get_line(Direction::Col, 1), vec![Cell::White, Cell::Black]);
+    /// ```
+    ///
+    pub fn get_line(&self, dir: Direction, idx: usize) -> Vec<Cell> {
+        match dir {
+            Direction::Row => self.cells[idx].clone(),
+            Direction::Col => self.cells.iter().map(|r| r[idx]).collect(),
+        }
+    }
+
+    ///
+    /// Sets row or column `idx` to values `vals`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::Unknown],
+    ///                 vec![Cell::Black, Cell::Unknown]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// picross.set_line(Direction::Col, 1, vec![Cell::White, Cell::Black]);
+    ///
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    pub fn set_line(&mut self, dir: Direction, idx: usize, vals: Vec<Cell>) {
+        match dir {
+            Direction::Row => self.set_row(idx, vals),
+            Direction::Col => self.set_col(idx, vals),
+        }
+    }
+
+    ///
+    /// Returns the specification of row or column `idx`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.line_spec(Direction::Row, 1), &vec![2]);
+    /// assert_eq!(picross.line_spec(Direction::Col, 1), &vec![1]);
+    /// ```
+    ///
+    pub fn line_spec(&self, dir: Direction, idx: usize) -> &Vec<usize> {
+        match dir {
+            Direction::Row => &self.row_spec[idx],
+            Direction::Col => &self.col_spec[idx],
+        }
+    }
+
+    ///
+    /// Returns an iterator over all the cells in row-major order
+    ///
+    /// The rows of `cells` are stored separately, so no flat slice can be
+    /// borrowed, but the iterator does not copy any cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Unknown, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(
+    ///     picross.iter_cells().cloned().collect::<Vec<Cell>>(),
+    ///     vec![Cell::Black, Cell::White, Cell::Unknown, Cell::Black]
+    /// );
+    /// ```
+    ///
+    pub fn iter_cells<'a>(&'a self) -> impl Iterator<Item=&'a Cell> + 'a {
+        self.cells.iter().flat_map(|r| r.iter())
+    }
+
+    ///
+    /// Returns an iterator over mutable references to all the cells in row-major
+    /// order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::Unknown],
+    ///                 vec![Cell::Unknown, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// for c in picross.iter_cells_mut().filter(|c| **c == Cell::Unknown) {
+    ///     *c = Cell::White;
+    /// }
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    pub fn iter_cells_mut<'a>(&'a mut self) -> impl Iterator<Item=&'a mut Cell> + 'a {
+        self.cells.iter_mut().flat_map(|r| r.iter_mut())
+    }
+
+    ///
+    /// Returns the cells orthogonally adjacent to the cell at row `row` and column
+    /// `col`, as `(row, col, cell)` triples in row-major order
+    ///
+    /// Cells on the border have fewer than 4 neighbors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not in the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Black, White, Unknown],
+    ///                 vec![White, Black, Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.cell_neighbors(0, 0), vec![(0, 1, &White), (1, 0, &White)]);
+    /// assert_eq!(picross.cell_neighbors(1, 1),
+    ///            vec![(0, 1, &White), (1, 0, &White), (1, 2, &Black)]);
+    /// ```
+    ///
+    pub fn cell_neighbors(&self, row: usize, col: usize) -> Vec<(usize, usize, &Cell)> {
+        if row >= self.height || col >= self.length {
+            panic!("Cell ({}, {}) is outside of the grid!", row, col);
+        }
+
+        let candidates = [
+            (row.checked_sub(1), Some(col)),
+            (Some(row), col.checked_sub(1)),
+            (Some(row), Some(col + 1)),
+            (Some(row + 1), Some(col)),
+        ];
+        candidates.iter().filter_map(|&(y, x)| match (y, x) {
+            (Some(y), Some(x)) if y < self.height && x < self.length => Some((y, x, &self.cells[y][x])),
+            _ => None,
+        }).collect()
+    }
+
+    /// Returns an iterator over the cells of rows `r0..r1` and columns `c0..c1`
+    fn region_cells<'a>(&'a self, r0: usize, c0: usize, r1: usize, c1: usize)
+            -> impl Iterator<Item=&'a Cell> + 'a {
+        self.cells[r0..r1].iter().flat_map(move |r| r[c0..c1].iter())
+    }
+
+    ///
+    /// Checks whether all the cells of rows `r0..r1` and columns `c0..c1` are black
+    ///
+    /// The bounds are exclusive, and an empty region is always all black. Hints are
+    /// treated as their value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region does not fit in the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 3,
+    ///     cells: vec![vec![Black, Black, White],
+    ///                 vec![Black, Black, White],
+    ///                 vec![White, White, Unknown]],
+    ///     row_spec: vec![vec![2], vec![2], vec![]],
+    ///     col_spec: vec![vec![2], vec![2], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.is_rectangular_region_all_black(0, 0, 2, 2));
+    /// assert!(!picross.is_rectangular_region_all_black(0, 0, 2, 3));
+    /// assert!(picross.is_region_all_white(0, 2, 2, 3));
+    /// assert!(!picross.is_region_all_white(0, 2, 3, 3));
+    /// assert!(picross.is_region_filled(0, 0, 3, 2));
+    /// assert!(!picross.is_region_filled(1, 1, 3, 3));
+    /// ```
+    ///
+    pub fn is_rectangular_region_all_black(&self, r0: usize, c0: usize, r1: usize, c1: usize) -> bool {
+        self.region_cells(r0, c0, r1, c1).all(|c| c.value() == Cell::Black)
+    }
+
+    ///
+    /// Checks whether all the cells of rows `r0..r1` and columns `c0..c1` are white
+    ///
+    /// See `is_rectangular_region_all_black` for details and an example.
+    ///
+    pub fn is_region_all_white(&self, r0: usize, c0: usize, r1: usize, c1: usize) -> bool {
+        self.region_cells(r0, c0, r1, c1).all(|c| c.value() == Cell::White)
+    }
+
+    ///
+    /// Checks whether none of the cells of rows `r0..r1` and columns `c0..c1` is
+    /// unknown
+    ///
+    /// See `is_rectangular_region_all_black` for details and an example.
+    ///
+    pub fn is_region_filled(&self, r0: usize, c0: usize, r1: usize, c1: usize) -> bool {
+        self.region_cells(r0, c0, r1, c1).all(|c| *c != Cell::Unknown)
+    }
+
+    ///
+    /// Sets all the cells of rows `r0..r1` and columns `c0..c1` to `cell`
+    ///
+    /// The bounds are exclusive. Returns an error, without changing any cell, if the
+    /// region does not fit in the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, Unknown};
+    /// use picross::error::OutOfBoundsError;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Unknown; 3]; 2],
+    ///     row_spec: vec![vec![2], vec![2]],
+    ///     col_spec: vec![vec![2], vec![2], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.set_region_cells(0, 0, 2, 2, Black), Ok(()));
+    /// assert_eq!(picross.cells, vec![vec![Black, Black, Unknown]; 2]);
+    ///
+    /// assert_eq!(picross.set_region_cells(1, 1, 3, 3, Black),
+    ///            Err(OutOfBoundsError { r0: 1, c0: 1, r1: 3, c1: 3, height: 2, length: 3 }));
+    /// ```
+    ///
+    pub fn set_region_cells(&mut self, r0: usize, c0: usize, r1: usize, c1: usize, cell: Cell)
+            -> Result<(), OutOfBoundsError> {
+        if r0 > r1 || c0 > c1 || r1 > self.height || c1 > self.length {
+            return Err(OutOfBoundsError { r0, c0, r1, c1, height: self.height, length: self.length });
+        }
+
+        for row in &mut self.cells[r0..r1] {
+            for c in &mut row[c0..c1] {
+                *c = cell;
+            }
+        }
+        Ok(())
+    }
+
     ///
     /// Transposes the cells
     ///
@@ -338,39 +814,247 @@ impl Picross {
 
         // Prepare an iterator that iterates over both lines and columns, coupled to specs
         let transpose = self.transpose();
-        let iter = self.row_spec.iter().zip(self.cells.iter())
+        let mut iter = self.row_spec.iter().zip(self.cells.iter())
             .chain(self.col_spec.iter().zip(transpose.iter()));
 
         // Check specs are matched
-        for (spec, line) in iter {
-            let mut num_block = 0;
-            let mut size_block = 0;
-            for c in line {
-                match c {
-                    &Cell::Unknown => return false,
-                    &Cell::Black   => size_block += 1,
-                    &Cell::White   => {
-                        if size_block > 0 {
-                            if num_block >= spec.len() || size_block != spec[num_block] {
-                                return false;
-                            }
-                            num_block += 1;
-                            size_block = 0;
-                        }
-                    }
-                }
-            }
-            if size_block > 0 {
-                if num_block >= spec.len() || size_block != spec[num_block] {
-                    return false;
+        iter.all(|(spec, line)| {
+            line.iter().all(|c| *c != Cell::Unknown) && line_runs(line) == *spec
+        })
+    }
+
+    ///
+    /// Checks whether the grid has no unknown cell left and matches its
+    /// specifications
+    ///
+    /// This is the same as `is_valid`, which already rejects unknown cells, but
+    /// reads better when checking the progress of a solver or of a player.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 1,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::Unknown]],
+    ///     row_spec: vec![vec![1]],
+    ///     col_spec: vec![vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(!picross.is_solved());
+    /// picross.cells[0][1] = Cell::White;
+    /// assert!(picross.is_solved());
+    /// ```
+    ///
+    pub fn is_solved(&self) -> bool {
+        !self.cells.iter().flatten().any(|c| *c == Cell::Unknown) && self.is_valid()
+    }
+
+    /// Checks that `height`, `length`, the cells and the specifications agree on the
+    /// dimensions
+    fn has_consistent_dimensions(&self) -> bool {
+        self.height == self.cells.len() && self.cells.iter().all(|r| self.length == r.len())
+            && self.height == self.row_spec.len() && self.length == self.col_spec.len()
+    }
+
+    ///
+    /// Checks that each row and each column has as many black cells as required by
+    /// its specification
+    ///
+    /// This is weaker than `is_valid`, as the blocks are not checked, but cheaper,
+    /// which makes it a useful pre-filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Black, Cell::White, Cell::Black],
+    ///                 vec![Cell::White, Cell::White, Cell::White]],
+    ///     row_spec: vec![vec![2], vec![]],
+    ///     col_spec: vec![vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// // The counts match, although the first row should have a block of size 2
+    /// assert!(picross.validate_completeness_against_spec());
+    /// assert!(!picross.is_valid());
+    ///
+    /// picross.cells[1][1] = Cell::Black;
+    /// assert!(!picross.validate_completeness_against_spec());
+    /// ```
+    ///
+    pub fn validate_completeness_against_spec(&self) -> bool {
+        if !self.has_consistent_dimensions() || !self.validate_row_col_black_counts_match() {
+            return false;
+        }
+
+        let mut col_blacks = vec![0; self.length];
+        for (spec, row) in self.row_spec.iter().zip(self.cells.iter()) {
+            let mut row_blacks = 0;
+            for (x, c) in row.iter().enumerate() {
+                if c.value() == Cell::Black {
+                    row_blacks += 1;
+                    col_blacks[x] += 1;
                 }
-                num_block += 1;
             }
-            if num_block != spec.len() {
+            if row_blacks != spec.iter().sum::<usize>() {
                 return false;
             }
-        };
+        }
+
+        self.col_spec.iter().zip(col_blacks.iter()).all(|(spec, &n)| spec.iter().sum::<usize>() == n)
+    }
+
+    ///
+    /// Checks that a Picross is valid, panicking with a detailed message otherwise
+    ///
+    /// This is intended for tests, where it gives more information than
+    /// `assert!(picross.is_valid())`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `is_valid` would return false, telling which row or column failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// picross.assert_valid();
+    /// ```
+    ///
+    /// ```should_panic(expected = "Column 1 failed: spec [2] but found [1]")
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// picross.assert_valid();
+    /// ```
+    ///
+    pub fn assert_valid(&self) {
+        if let Some(failure) = self.diagnose_invalid().first() {
+            panic!("{}", failure);
+        }
+    }
 
-        true
+    ///
+    /// Lists all the reasons for a Picross not to be valid
+    ///
+    /// Returns an empty vector if and only if `is_valid` returns true. If the
+    /// dimensions are inconsistent, only `DimensionMismatch` is returned. Otherwise,
+    /// all the unknown cells are returned, along with the rows and columns with no
+    /// unknown cell that do not match their specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, ValidationFailure};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::Unknown],
+    ///                 vec![Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![2], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.diagnose_invalid(), vec![
+    ///     ValidationFailure::UnknownCell { row: 0, col: 1 },
+    ///     ValidationFailure::RowSpecNotMatched { row: 1, spec: vec![1], found: vec![2] },
+    /// ]);
+    /// ```
+    ///
+    pub fn diagnose_invalid(&self) -> Vec<ValidationFailure> {
+        if !self.has_consistent_dimensions() {
+            return vec![ValidationFailure::DimensionMismatch];
+        }
+
+        let mut res = vec![];
+
+        for (row, (spec, line)) in self.row_spec.iter().zip(self.cells.iter()).enumerate() {
+            let unknowns = line.iter().enumerate()
+                               .filter(|&(_, c)| *c == Cell::Unknown)
+                               .map(|(col, _)| ValidationFailure::UnknownCell { row, col })
+                               .collect::<Vec<ValidationFailure>>();
+            let found = line_runs(line);
+            if unknowns.is_empty() && found != *spec {
+                res.push(ValidationFailure::RowSpecNotMatched { row, spec: spec.clone(), found });
+            }
+            res.extend(unknowns);
+        }
+
+        for (col, (spec, line)) in self.col_spec.iter().zip(self.transpose().iter()).enumerate() {
+            let found = line_runs(line);
+            if line.iter().all(|c| *c != Cell::Unknown) && found != *spec {
+                res.push(ValidationFailure::ColSpecNotMatched { col, spec: spec.clone(), found });
+            }
+        }
+
+        res
+    }
+
+    ///
+    /// Checks that a Picross is valid, returning all the validation failures
+    /// otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, ValidationFailure};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::Unknown]],
+    ///     row_spec: vec![vec![1]],
+    ///     col_spec: vec![vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let err = picross.validate().unwrap_err();
+    /// assert_eq!(err.0, vec![ValidationFailure::UnknownCell { row: 0, col: 1 }]);
+    /// assert_eq!(err.to_string(), "Invalid grid: Cell at row 0, column 1 is unknown");
+    /// ```
+    ///
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let failures = self.diagnose_invalid();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError(failures))
+        }
     }
 }
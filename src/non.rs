@@ -0,0 +1,212 @@
+use ::{Cell, Picross, PuzzleMetadata};
+use parse::ParseError;
+
+/// Parses a comma-separated clue line, where `0` stands for an empty line
+fn parse_clues(line: &str) -> Result<Vec<usize>, ParseError> {
+    let clues = line.split(',').map(|f| f.trim().parse::<usize>().map_err(|_| {
+        ParseError(format!("Expected integer clue and found '{}' in '{}'", f, line))
+    })).collect::<Result<Vec<usize>, ParseError>>()?;
+    Ok(clues.into_iter().filter(|&c| c != 0).collect())
+}
+
+/// Parses the value of the `key` header line
+fn parse_dimension(value: Option<&str>, key: &str) -> Result<usize, ParseError> {
+    value.and_then(|v| v.trim().parse::<usize>().ok())
+         .ok_or(ParseError(format!("Expected an integer after '{}'", key)))
+}
+
+/// Returns the value of a text line, without its surrounding double quotes
+fn parse_text(value: Option<&str>) -> Option<String> {
+    value.map(|v| v.trim().trim_matches('"').to_string())
+}
+
+impl Picross {
+    ///
+    /// Serializes the grid in the `.non` format of Steve Simpson's nonogram solver,
+    /// which many other nonogram programs also read
+    ///
+    /// The format is made of lines ending with `\n`:
+    ///
+    /// - `title`, `by` and `copyright` lines, for the fields of `meta` that are
+    ///   set, their value being between double quotes;
+    /// - `width` and `height` lines, giving the dimensions;
+    /// - a `rows` line followed by the clues of each row, then a `columns` line
+    ///   followed by the clues of each column, clues being separated by commas and
+    ///   empty lines being written `0`;
+    /// - if the grid is solved, a `goal` line holding the cells row by row between
+    ///   double quotes, `1` for black and `0` for white.
+    ///
+    /// Blocks are separated by an empty line. The description of `meta` has no
+    /// counterpart in the format and is not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, PuzzleMetadata};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![1, 1], vec![]],
+    ///     col_spec: vec![vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let res = "width 3\nheight 2\n\nrows\n1,1\n0\n\ncolumns\n1\n0\n1\n";
+    /// assert_eq!(picross.to_non_format(&PuzzleMetadata::default()), res);
+    ///
+    /// picross.solve();
+    /// let meta = PuzzleMetadata {
+    ///     title: Some("Eyes".to_string()),
+    ///     ..PuzzleMetadata::default()
+    /// };
+    /// let res = "title \"Eyes\"\n".to_string() + res + "\ngoal \"101000\"\n";
+    /// assert_eq!(picross.to_non_format(&meta), res);
+    /// ```
+    ///
+    pub fn to_non_format(&self, meta: &PuzzleMetadata) -> String {
+        let clues = |specs: &Vec<Vec<usize>>| {
+            specs.iter().map(|s| {
+                if s.is_empty() {
+                    "0\n".to_string()
+                } else {
+                    s.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",") + "\n"
+                }
+            }).collect::<String>()
+        };
+
+        let mut res = String::new();
+        let fields = [("title", &meta.title), ("by", &meta.author), ("copyright", &meta.copyright)];
+        for &(key, value) in &fields {
+            if let Some(ref value) = *value {
+                res.push_str(&format!("{} \"{}\"\n", key, value));
+            }
+        }
+        res.push_str(&format!("width {}\nheight {}\n\n", self.length, self.height));
+        res.push_str("rows\n");
+        res.push_str(&clues(&self.row_spec));
+        res.push_str("\ncolumns\n");
+        res.push_str(&clues(&self.col_spec));
+
+        if self.is_valid() {
+            let goal = self.cells.iter()
+                .flat_map(|r| r.iter())
+                .map(|c| if c.value() == Cell::Black { '1' } else { '0' })
+                .collect::<String>();
+            res.push_str(&format!("\ngoal \"{}\"\n", goal));
+        }
+        res
+    }
+
+    ///
+    /// Parses a grid in the `.non` format described in `to_non_format`
+    ///
+    /// Empty lines and lines with unknown keys, like `catalogue`, are ignored. The
+    /// goal may be written with or without double quotes. The cells are set to the
+    /// goal if there is one, and are all `Cell::Unknown` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let s = "catalogue \"#1\"\nwidth 2\nheight 1\nrows\n1\ncolumns\n0\n1\ngoal 01\n";
+    /// let picross = Picross::from_non_format(s).unwrap();
+    /// assert_eq!(picross.row_spec, vec![vec![1]]);
+    /// assert_eq!(picross.col_spec, vec![vec![], vec![1]]);
+    /// assert_eq!(picross.cells, vec![vec![Cell::White, Cell::Black]]);
+    ///
+    /// assert!(Picross::from_non_format("width 2\nheight 1\nrows\n1\n").is_err());
+    /// ```
+    ///
+    pub fn from_non_format(s: &str) -> Result<Picross, ParseError> {
+        Picross::from_non_format_with_metadata(s).map(|(p, _)| p)
+    }
+
+    ///
+    /// Parses a grid in the `.non` format, along with the metadata of its `title`,
+    /// `by` and `copyright` lines
+    ///
+    /// See `from_non_format` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::Picross;
+    ///
+    /// let s = "title \"Dot\"\nby \"Jane\"\nwidth 1\nheight 1\nrows\n1\ncolumns\n1\n";
+    /// let (picross, meta) = Picross::from_non_format_with_metadata(s).unwrap();
+    /// assert_eq!(picross.row_spec, vec![vec![1]]);
+    /// assert_eq!(meta.title, Some("Dot".to_string()));
+    /// assert_eq!(meta.author, Some("Jane".to_string()));
+    /// assert_eq!(meta.copyright, None);
+    /// ```
+    ///
+    pub fn from_non_format_with_metadata(s: &str) -> Result<(Picross, PuzzleMetadata), ParseError> {
+        let mut meta = PuzzleMetadata::default();
+        let mut length = None;
+        let mut height = None;
+        let mut row_spec = vec![];
+        let mut col_spec = vec![];
+        let mut goal = None;
+
+        let mut lines = s.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).peekable();
+        while let Some(line) = lines.next() {
+            let mut words = line.splitn(2, ' ');
+            match words.next() {
+                Some("title")     => meta.title = parse_text(words.next()),
+                Some("by")        => meta.author = parse_text(words.next()),
+                Some("copyright") => meta.copyright = parse_text(words.next()),
+                Some("width")     => length = Some(parse_dimension(words.next(), "width")?),
+                Some("height")    => height = Some(parse_dimension(words.next(), "height")?),
+                Some(key @ "rows") | Some(key @ "columns") => {
+                    let specs = if key == "rows" { &mut row_spec } else { &mut col_spec };
+                    while let Some(clues) = lines.peek() {
+                        if !clues.starts_with(|c: char| c.is_ascii_digit()) {
+                            break;
+                        }
+                        specs.push(parse_clues(clues)?);
+                        lines.next();
+                    }
+                }
+                Some("goal") => goal = parse_text(words.next()),
+                _ => (),
+            }
+        }
+
+        let height = height.ok_or(ParseError("Expected a height line".to_string()))?;
+        let length = length.ok_or(ParseError("Expected a width line".to_string()))?;
+        if row_spec.len() != height || col_spec.len() != length {
+            return Err(ParseError(format!("Expected {} rows and {} columns but found {} and {}",
+                                          height, length, row_spec.len(), col_spec.len())));
+        }
+
+        let cells = match goal {
+            None       => vec![vec![Cell::Unknown; length]; height],
+            Some(goal) => {
+                if goal.len() != height * length {
+                    return Err(ParseError(format!("Expected a goal of {} cells and found '{}'",
+                                                  height * length, goal)));
+                }
+                let cells = goal.chars().map(|c| match c {
+                    '1' => Ok(Cell::Black),
+                    '0' => Ok(Cell::White),
+                    _   => Err(ParseError(format!("Expected 0 or 1 and found '{}' in goal", c))),
+                }).collect::<Result<Vec<Cell>, ParseError>>()?;
+                cells.chunks(length.max(1)).map(|r| r.to_vec()).collect()
+            }
+        };
+
+        Ok((Picross {
+            height,
+            length,
+            cells,
+            row_spec,
+            col_spec,
+            possible_rows: vec![],
+            possible_cols: vec![],
+        }, meta))
+    }
+}
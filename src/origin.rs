@@ -0,0 +1,130 @@
+use ::{Cell, Picross};
+use solve::{Contradiction, SearchHooks};
+
+/// How the value of a cell was found by `Picross::solve_by_color_coding`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CellOrigin {
+    /// The cell was known before solving
+    Given,
+    /// The cell was deduced by line solving, before any guess
+    LineSolving,
+    /// The cell was guessed, or deduced by line solving after a guess
+    Backtracking,
+    /// The cell was guessed, but its first guess led to a contradiction, so that its
+    /// value was learned from the conflict
+    Learning,
+    /// The value of the cell is still unknown
+    Unknown,
+}
+
+impl CellOrigin {
+    /// Returns the ANSI escape code used to color cells of this origin, if any
+    pub(crate) fn ansi_color(self) -> Option<&'static str> {
+        match self {
+            CellOrigin::Given        => None,
+            CellOrigin::LineSolving  => Some("\x1b[32m"),
+            CellOrigin::Backtracking => Some("\x1b[38;5;208m"),
+            CellOrigin::Learning     => Some("\x1b[31m"),
+            CellOrigin::Unknown      => Some("\x1b[90m"),
+        }
+    }
+}
+
+/// Hooks of `Picross::solve_by_color_coding`, recording in `origins` how each cell
+/// of the current node was found
+struct OriginHooks {
+    origins: Vec<Vec<CellOrigin>>,
+    /// Value of `origins` before each guess on the path to the current node
+    saved: Vec<Vec<Vec<CellOrigin>>>,
+}
+
+impl SearchHooks for OriginHooks {
+    fn propagate(&mut self, picross: &mut Picross) -> Result<(), Contradiction> {
+        let before = picross.cells.clone();
+        picross.propagate()?;
+
+        let deduced = if self.saved.is_empty() { CellOrigin::LineSolving } else { CellOrigin::Backtracking };
+        for (y, (row, before_row)) in picross.cells.iter().zip(&before).enumerate() {
+            for (x, (c, b)) in row.iter().zip(before_row).enumerate() {
+                if c != b {
+                    self.origins[y][x] = deduced;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn guess(&mut self, _guess: &Picross, y: usize, x: usize, c: Cell) -> bool {
+        self.saved.push(self.origins.clone());
+        self.origins[y][x] = if c == Cell::Black { CellOrigin::Backtracking } else { CellOrigin::Learning };
+        true
+    }
+
+    fn backtrack(&mut self, _y: usize, _x: usize, _c: Cell) {
+        self.origins = self.saved.pop().expect("Backtracking from a guess that was made");
+    }
+}
+
+impl Picross {
+
+    ///
+    /// Solves the grid like `solve`, and returns how the value of each cell was
+    /// found, for visual debugging
+    ///
+    /// The result can be displayed with `PicrossPrinter::with_origins`, which colors
+    /// the cells with ANSI escape codes: green for line solving, orange for
+    /// backtracking, red for values learned from a conflict, and grey for unknown
+    /// cells. If the grid is unsolvable, the cells are left untouched and only
+    /// `CellOrigin::Given` and `CellOrigin::Unknown` are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::display::PicrossPrinter;
+    /// use picross::origin::CellOrigin::{Given, LineSolving, Backtracking};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 3,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 3],
+    ///     row_spec: vec![vec![3], vec![1], vec![1]],
+    ///     col_spec: vec![vec![1, 1], vec![1], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// picross.cells[1][0] = Cell::White;
+    ///
+    /// let origins = picross.solve_by_color_coding();
+    /// assert!(picross.is_valid());
+    /// assert_eq!(origins[0], vec![LineSolving; 3]);
+    /// assert_eq!(origins[1][0], Given);
+    ///
+    /// let rendered = PicrossPrinter::new(&picross).with_origins(&origins).to_string();
+    /// assert!(rendered.contains("\x1b[32m#\x1b[0m"));
+    ///
+    /// // This grid has two solutions, so a guess is needed
+    /// picross.height = 2;
+    /// picross.length = 2;
+    /// picross.cells = vec![vec![Cell::Unknown; 2]; 2];
+    /// picross.row_spec = vec![vec![1], vec![1]];
+    /// picross.col_spec = vec![vec![1], vec![1]];
+    /// assert_eq!(picross.solve_by_color_coding(), vec![vec![Backtracking; 2]; 2]);
+    /// ```
+    ///
+    pub fn solve_by_color_coding(&mut self) -> Vec<Vec<CellOrigin>> {
+        let origins = self.cells.iter().map(|r| {
+            r.iter().map(|c| if *c == Cell::Unknown { CellOrigin::Unknown } else { CellOrigin::Given })
+             .collect()
+        }).collect::<Vec<Vec<CellOrigin>>>();
+
+        let mut picross = self.clone();
+        let mut hooks = OriginHooks { origins: origins.clone(), saved: vec![] };
+        if picross.backtrack(&mut hooks) {
+            *self = picross;
+            hooks.origins
+        } else {
+            origins
+        }
+    }
+}
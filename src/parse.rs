@@ -1,7 +1,14 @@
 use std::borrow::Borrow;
 
+use thiserror::Error;
+
 use ::{Cell, Picross};
 
+/// Error returned when some input cannot be parsed into a Picross grid
+#[derive(Clone, PartialEq, Debug, Error)]
+#[error("{0}")]
+pub struct ParseError(pub String);
+
 impl Picross {
     ///
     /// /!\ Intended for internal use only /!\
@@ -39,23 +46,40 @@ impl Picross {
     /// ```
     ///
     pub fn get_specs<T: Borrow<str>>(s: T) -> Vec<usize> {
+        Picross::try_get_specs(s).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    ///
+    /// /!\ Intended for internal use only /!\
+    ///
+    /// Parses `s` according to the format [1,2,4...], like `get_specs` but returning
+    /// an error instead of panicking
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(picross::Picross::try_get_specs("[2,1]"), Ok(vec![2, 1]));
+    /// assert!(picross::Picross::try_get_specs("[a,2]").is_err());
+    /// ```
+    ///
+    pub fn try_get_specs<T: Borrow<str>>(s: T) -> Result<Vec<usize>, ParseError> {
         let s = s.borrow();
 
-        if s.len() < 2 || s[0..1].to_string() != "[" || s[s.len() - 1 .. s.len()].to_string() != "]" {
-            panic!("Expected '{}' to be of form [1,4,3...]", s);
+        if !s.starts_with('[') || !s.ends_with(']') || s.len() < 2 {
+            return Err(ParseError(format!("Expected '{}' to be of form [1,4,3...]", s)));
         }
 
         let s = &s[1 .. s.len() - 1];
 
-        if s.len() == 0 {
-            return vec![];
+        if s.is_empty() {
+            return Ok(vec![]);
         }
 
         s.split(',')
-         .map(|x| x.parse::<usize>()
-                   .ok()
-                   .expect(&format!("Expected integer and found '{}' in '{}'", x, s)))
-         .collect::<Vec<usize>>()
+         .map(|x| x.parse::<usize>().map_err(|_| {
+             ParseError(format!("Expected integer and found '{}' in '{}'", x, s))
+         }))
+         .collect()
     }
 
     ///
@@ -176,4 +200,87 @@ impl Picross {
 
         res
     }
+
+    ///
+    /// Builds a Picross grid from the specifications and the cells drawn in `s`,
+    /// one row per line, with the given characters
+    ///
+    /// This is the inverse of `to_grid_string`. Each line must have exactly one
+    /// character per column, so trailing white cells must not be stripped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross::from_grid_string("X.\n?X\n", 'X', '.', '?',
+    ///                                         vec![vec![1], vec![1]],
+    ///                                         vec![vec![1], vec![1]]).unwrap();
+    /// assert_eq!(picross.cells, vec![vec![Cell::Black, Cell::White],
+    ///                                vec![Cell::Unknown, Cell::Black]]);
+    ///
+    /// assert!(Picross::from_grid_string("X.\n", 'X', '.', '?',
+    ///                                   vec![vec![1], vec![1]],
+    ///                                   vec![vec![1], vec![1]]).is_err());
+    /// ```
+    ///
+    pub fn from_grid_string(s: &str, black: char, white: char, unknown: char,
+                            row_spec: Vec<Vec<usize>>, col_spec: Vec<Vec<usize>>)
+            -> Result<Picross, ParseError> {
+        let cells = s.lines().map(|l| {
+            l.chars().map(|c| match c {
+                c if c == black   => Ok(Cell::Black),
+                c if c == white   => Ok(Cell::White),
+                c if c == unknown => Ok(Cell::Unknown),
+                c                 => Err(ParseError(format!("Unexpected '{}' in '{}'", c, l))),
+            }).collect::<Result<Vec<Cell>, ParseError>>()
+        }).collect::<Result<Vec<Vec<Cell>>, ParseError>>()?;
+
+        let height = row_spec.len();
+        let length = col_spec.len();
+        if cells.len() != height || cells.iter().any(|r| r.len() != length) {
+            return Err(ParseError(format!("Expected {} lines of {} cells", height, length)));
+        }
+
+        Ok(Picross {
+            height,
+            length,
+            cells,
+            row_spec,
+            col_spec,
+            possible_rows: vec![],
+            possible_cols: vec![],
+        })
+    }
+
+    ///
+    /// Builds a Picross grid like `from_grid_string`, guessing the characters used
+    /// to draw the cells
+    ///
+    /// The black, white and unknown characters `#`, ` `, `?` are tried first, then
+    /// `#`, `.`, `?`, then `X`, `.`, `?`, then `1`, `0`, `?`, then `▓`, `░`, `·`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross::from_grid_string_auto("10\n?1\n",
+    ///                                              vec![vec![1], vec![1]],
+    ///                                              vec![vec![1], vec![1]]).unwrap();
+    /// assert_eq!(picross.cells, vec![vec![Cell::Black, Cell::White],
+    ///                                vec![Cell::Unknown, Cell::Black]]);
+    /// ```
+    ///
+    pub fn from_grid_string_auto(s: &str, row_spec: Vec<Vec<usize>>, col_spec: Vec<Vec<usize>>)
+            -> Result<Picross, ParseError> {
+        let formats = [('#', ' ', '?'), ('#', '.', '?'), ('X', '.', '?'), ('1', '0', '?'),
+                       ('▓', '░', '·')];
+        formats.iter()
+               .filter_map(|&(b, w, u)| {
+                   Picross::from_grid_string(s, b, w, u, row_spec.clone(), col_spec.clone()).ok()
+               })
+               .next()
+               .ok_or(ParseError("Could not recognize the characters of the grid".to_string()))
+    }
 }
@@ -0,0 +1,339 @@
+use quick_xml;
+use quick_xml::Reader;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+
+use ::{Cell, Picross, PuzzleMetadata};
+use parse::ParseError;
+
+impl From<quick_xml::Error> for ParseError {
+    fn from(e: quick_xml::Error) -> ParseError {
+        ParseError(format!("Invalid XML: {}", e))
+    }
+}
+
+/// Returns the unescaped value of attribute `name` of `e`, if any
+fn attribute(e: &BytesStart, name: &str) -> Result<Option<String>, ParseError> {
+    match e.try_get_attribute(name)? {
+        Some(a) => Ok(Some(a.unescape_value()?.into_owned())),
+        None    => Ok(None),
+    }
+}
+
+/// Parses the content of a PBN `<image>` element into cells
+fn parse_image(image: &str, black: char, white: char) -> Result<Vec<Vec<Cell>>, ParseError> {
+    image.lines()
+         .map(|l| l.trim())
+         .filter(|l| !l.is_empty())
+         .map(|l| {
+             l.trim_matches('|').chars().map(|c| match c {
+                 '?'               => Ok(Cell::Unknown),
+                 c if c == black   => Ok(Cell::Black),
+                 c if c == white   => Ok(Cell::White),
+                 c                 => Err(ParseError(format!("Unexpected '{}' in image", c))),
+             }).collect()
+         })
+         .collect()
+}
+
+impl Picross {
+    ///
+    /// Serializes a Picross grid into the PBN (Puzzle Binary Notation) XML format
+    ///
+    /// Only the monochrome subset of the format is used. The solution is written
+    /// only if the grid has no unknown cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, PuzzleMetadata};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Black, Cell::White],
+    ///                 vec![Cell::Black, Cell::Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let meta = PuzzleMetadata {
+    ///     title: Some("Corner".to_string()),
+    ///     ..PuzzleMetadata::default()
+    /// };
+    ///
+    /// let pbn = picross.to_pbn(&meta);
+    /// assert!(pbn.contains("<title>Corner</title>"));
+    /// assert!(pbn.contains("<line><count>2</count></line>"));
+    ///
+    /// let (parsed, parsed_meta) = Picross::from_pbn_with_metadata(&pbn).unwrap();
+    /// assert_eq!(parsed.row_spec, picross.row_spec);
+    /// assert_eq!(parsed.col_spec, picross.col_spec);
+    /// assert_eq!(parsed.cells, picross.cells);
+    /// assert_eq!(parsed_meta, meta);
+    /// ```
+    ///
+    pub fn to_pbn(&self, meta: &PuzzleMetadata) -> String {
+        pbn_puzzleset(&[(meta, self)])
+    }
+
+    /// Serializes the grid into a PBN `<puzzle>` element
+    fn to_pbn_puzzle(&self, meta: &PuzzleMetadata) -> String {
+        let mut res = String::new();
+        res.push_str("<puzzle type=\"grid\" defaultcolor=\"black\">\n");
+
+        let fields = [("title", &meta.title), ("author", &meta.author),
+                      ("copyright", &meta.copyright), ("description", &meta.description)];
+        for &(tag, value) in &fields {
+            if let Some(ref value) = *value {
+                res.push_str(&format!("<{}>{}</{}>\n", tag, escape(value), tag));
+            }
+        }
+
+        res.push_str("<colors>\n");
+        res.push_str("<color name=\"white\" char=\".\">fff</color>\n");
+        res.push_str("<color name=\"black\" char=\"X\">000</color>\n");
+        res.push_str("</colors>\n");
+
+        for &(kind, specs) in &[("columns", &self.col_spec), ("rows", &self.row_spec)] {
+            res.push_str(&format!("<clues type=\"{}\">\n", kind));
+            for spec in specs {
+                res.push_str("<line>");
+                for x in spec {
+                    res.push_str(&format!("<count>{}</count>", x));
+                }
+                res.push_str("</line>\n");
+            }
+            res.push_str("</clues>\n");
+        }
+
+        if self.cells.iter().all(|r| r.iter().all(|c| *c != Cell::Unknown)) {
+            res.push_str("<solution type=\"goal\">\n<image>\n");
+            for row in &self.cells {
+                res.push('|');
+                res.extend(row.iter().map(|c| if c.value() == Cell::Black { 'X' } else { '.' }));
+                res.push_str("|\n");
+            }
+            res.push_str("</image>\n</solution>\n");
+        }
+
+        res.push_str("</puzzle>\n");
+        res
+    }
+
+    ///
+    /// Parses the first puzzle of a PBN (Puzzle Binary Notation) XML document
+    ///
+    /// Only the monochrome subset of the format is supported. If the document
+    /// contains a goal solution, the cells are filled with it, otherwise they are
+    /// all `Cell::Unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let pbn = r#"<?xml version="1.0"?>
+    /// <puzzleset>
+    ///   <puzzle type="grid" defaultcolor="black">
+    ///     <clues type="columns">
+    ///       <line><count>1</count></line>
+    ///       <line/>
+    ///     </clues>
+    ///     <clues type="rows">
+    ///       <line></line>
+    ///       <line><count>1</count></line>
+    ///     </clues>
+    ///   </puzzle>
+    /// </puzzleset>"#;
+    ///
+    /// let picross = Picross::from_pbn(pbn).unwrap();
+    /// assert_eq!(picross.row_spec, vec![vec![], vec![1]]);
+    /// assert_eq!(picross.col_spec, vec![vec![1], vec![]]);
+    /// assert_eq!(picross.cells, vec![vec![Cell::Unknown; 2]; 2]);
+    ///
+    /// assert!(Picross::from_pbn("<puzzleset></puzzleset>").is_err());
+    /// ```
+    ///
+    pub fn from_pbn(xml: &str) -> Result<Picross, ParseError> {
+        Picross::from_pbn_with_metadata(xml).map(|(p, _)| p)
+    }
+
+    ///
+    /// Parses the first puzzle of a PBN (Puzzle Binary Notation) XML document,
+    /// along with its metadata
+    ///
+    /// See `from_pbn` and `to_pbn` for details and examples.
+    ///
+    pub fn from_pbn_with_metadata(xml: &str) -> Result<(Picross, PuzzleMetadata), ParseError> {
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        next_pbn_puzzle(&mut reader, &mut vec![])?.ok_or(ParseError("No puzzle found".to_string()))
+    }
+}
+
+/// Serializes grids into a PBN (Puzzle Binary Notation) XML document
+pub(crate) fn pbn_puzzleset(puzzles: &[(&PuzzleMetadata, &Picross)]) -> String {
+    let mut res = String::new();
+    res.push_str("<?xml version=\"1.0\"?>\n");
+    res.push_str("<!DOCTYPE pbn SYSTEM \"http://webpbn.com/pbn-0.3.dtd\">\n");
+    res.push_str("<puzzleset>\n");
+    for &(meta, picross) in puzzles {
+        res.push_str(&picross.to_pbn_puzzle(meta));
+    }
+    res.push_str("</puzzleset>\n");
+    res
+}
+
+/// Parses all the puzzles of a PBN (Puzzle Binary Notation) XML document, along
+/// with their metadata
+pub(crate) fn parse_pbn_puzzleset(xml: &str) -> Result<Vec<(Picross, PuzzleMetadata)>, ParseError> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    let mut path = vec![];
+    let mut res = vec![];
+    while let Some(puzzle) = next_pbn_puzzle(&mut reader, &mut path)? {
+        res.push(puzzle);
+    }
+    Ok(res)
+}
+
+/// Parses the next puzzle read by `reader`, `path` holding the names of the open
+/// elements
+///
+/// Returns `None` if there is no puzzle left.
+fn next_pbn_puzzle(reader: &mut Reader<&[u8]>, path: &mut Vec<String>)
+        -> Result<Option<(Picross, PuzzleMetadata)>, ParseError> {
+    let mut meta = PuzzleMetadata::default();
+    let mut row_spec: Option<Vec<Vec<usize>>> = None;
+    let mut col_spec: Option<Vec<Vec<usize>>> = None;
+    let mut image: Option<String> = None;
+    let (mut black, mut white) = ('X', '.');
+
+    // Type of the clues or solution being read
+    let mut clues_type = String::new();
+    let mut solution_type = String::new();
+    let mut lines: Vec<Vec<usize>> = vec![];
+    let mut seen_puzzle = false;
+
+    loop {
+        let (start, empty) = match reader.read_event()? {
+            Event::Start(e) => (Some(e), false),
+            Event::Empty(e) => (Some(e), true),
+            Event::End(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                if name == "clues" && path.len() == 3 {
+                    match clues_type.as_str() {
+                        "rows"    => row_spec = Some(lines.clone()),
+                        "columns" => col_spec = Some(lines.clone()),
+                        t         => return Err(ParseError(format!("Unknown clues type '{}'", t))),
+                    }
+                }
+                if name == "puzzle" && path.len() == 2 {
+                    path.pop();
+                    break;
+                }
+                path.pop();
+                continue;
+            }
+            Event::Text(t) => {
+                let text = t.unescape()?.into_owned();
+                let parent = path.last().map(|s| s.as_str()).unwrap_or("");
+                match (path.len(), parent) {
+                    (3, "title")       => meta.title = Some(text),
+                    (3, "author")      => meta.author = Some(text),
+                    (3, "copyright")   => meta.copyright = Some(text),
+                    (3, "description") => meta.description = Some(text),
+                    (5, "count")       => {
+                        let count = text.parse().map_err(|_| {
+                            ParseError(format!("Expected integer count and found '{}'", text))
+                        })?;
+                        match lines.last_mut() {
+                            Some(l) => l.push(count),
+                            None    => return Err(ParseError("Found count outside of a line".to_string())),
+                        }
+                    }
+                    (4, "image") if solution_type == "goal" && image.is_none() => {
+                        image = Some(text)
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        let e = start.expect("Start or empty element");
+        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+        match (path.len(), name.as_str()) {
+            (1, "puzzle") => {
+                seen_puzzle = true;
+                if let Some(t) = attribute(&e, "type")? {
+                    if t != "grid" {
+                        return Err(ParseError(format!("Unsupported puzzle type '{}'", t)));
+                    }
+                }
+            }
+            (2, "clues") => {
+                clues_type = attribute(&e, "type")?.unwrap_or_default();
+                lines = vec![];
+            }
+            (2, "solution") => {
+                solution_type = attribute(&e, "type")?.unwrap_or("goal".to_string());
+            }
+            (3, "color") => {
+                let c = attribute(&e, "char")?.and_then(|c| c.chars().next());
+                match (attribute(&e, "name")?, c) {
+                    (Some(ref n), Some(c)) if n == "black" => black = c,
+                    (Some(ref n), Some(c)) if n == "white" => white = c,
+                    _ => {}
+                }
+            }
+            (3, "line") => lines.push(vec![]),
+            (4, "count") => {
+                if let Some(c) = attribute(&e, "color")? {
+                    if c != "black" {
+                        return Err(ParseError("Color clues are not supported".to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+        if !empty {
+            path.push(name);
+        }
+    }
+
+    if !seen_puzzle {
+        return Ok(None);
+    }
+    let row_spec = row_spec.ok_or(ParseError("No row clues found".to_string()))?;
+    let col_spec = col_spec.ok_or(ParseError("No column clues found".to_string()))?;
+    let height = row_spec.len();
+    let length = col_spec.len();
+
+    let cells = match image {
+        Some(image) => {
+            let cells = parse_image(&image, black, white)?;
+            if cells.len() != height || cells.iter().any(|r| r.len() != length) {
+                return Err(ParseError("Solution image has wrong dimensions".to_string()));
+            }
+            cells
+        }
+        None => vec![vec![Cell::Unknown; length]; height],
+    };
+
+    let picross = Picross {
+        height,
+        length,
+        cells,
+        row_spec,
+        col_spec,
+        possible_rows: vec![],
+        possible_cols: vec![],
+    };
+    Ok(Some((picross, meta)))
+}
@@ -0,0 +1,157 @@
+use ::{Cell, Picross};
+use solve::{SearchHooks, SolveResult};
+
+/// Weights telling `Picross::solve_profile_guided` which cells to guess first,
+/// usually learned with `SolveProfile::learn_from_puzzles`
+#[derive(Clone, PartialEq, Debug)]
+pub struct SolveProfile {
+    /// Number of rows of the weight matrix
+    pub height: usize,
+    /// Number of columns of the weight matrix
+    pub length: usize,
+    /// Weight of each cell, the unknown cell with the highest weight being guessed
+    /// first
+    pub weights: Vec<Vec<f64>>,
+}
+
+impl SolveProfile {
+    ///
+    /// Learns which cells are most often deduced by line solving alone in
+    /// `puzzles`
+    ///
+    /// Each puzzle is line solved from scratch, ignoring its current cells. The
+    /// weight of a cell is the fraction of the puzzles containing it where line
+    /// solving determined it. The profile is as large as the largest puzzles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::profile::SolveProfile;
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// let mut ambiguous = picross.clone();
+    /// ambiguous.row_spec = vec![vec![1], vec![1]];
+    ///
+    /// let profile = SolveProfile::learn_from_puzzles(&[picross, ambiguous]);
+    /// assert_eq!(profile.weights, vec![vec![0.5; 2]; 2]);
+    /// ```
+    ///
+    pub fn learn_from_puzzles(puzzles: &[Picross]) -> SolveProfile {
+        let height = puzzles.iter().map(|p| p.height).max().unwrap_or(0);
+        let length = puzzles.iter().map(|p| p.length).max().unwrap_or(0);
+        let mut determined = vec![vec![0usize; length]; height];
+        let mut seen = vec![vec![0usize; length]; height];
+
+        for puzzle in puzzles {
+            let mut picross = puzzle.clone();
+            picross.cells = vec![vec![Cell::Unknown; picross.length]; picross.height];
+            let _ = picross.propagate();
+            for (y, row) in picross.cells.iter().enumerate() {
+                for (x, c) in row.iter().enumerate() {
+                    seen[y][x] += 1;
+                    if *c != Cell::Unknown {
+                        determined[y][x] += 1;
+                    }
+                }
+            }
+        }
+
+        let weights = determined.iter().zip(seen.iter()).map(|(d_row, s_row)| {
+            d_row.iter().zip(s_row.iter())
+                 .map(|(&d, &s)| if s == 0 { 0. } else { d as f64 / s as f64 })
+                 .collect()
+        }).collect();
+        SolveProfile { height, length, weights }
+    }
+
+    /// Returns the weight of the cell at row `y` and column `x`, 0 if it is outside
+    /// of the profile
+    fn weight(&self, y: usize, x: usize) -> f64 {
+        self.weights.get(y).and_then(|r| r.get(x)).cloned().unwrap_or(0.)
+    }
+}
+
+/// Hooks of `Picross::solve_profile_guided`, guessing the cells in the order given
+/// by the profile
+struct ProfileHooks<'a>(&'a SolveProfile);
+
+impl<'a> SearchHooks for ProfileHooks<'a> {
+    fn choose(&mut self, picross: &Picross) -> Option<(usize, usize)> {
+        picross.profile_guess(self.0)
+    }
+}
+
+impl Picross {
+    /// Returns the unknown cell with the highest weight in `profile`, the first one
+    /// in row-major order in case of a tie
+    fn profile_guess(&self, profile: &SolveProfile) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        for y in 0..self.height {
+            for x in 0..self.length {
+                if self.cells[y][x] != Cell::Unknown {
+                    continue;
+                }
+                match best {
+                    Some((by, bx)) if profile.weight(by, bx) >= profile.weight(y, x) => (),
+                    _ => best = Some((y, x)),
+                }
+            }
+        }
+        best
+    }
+
+    ///
+    /// Solves the grid like `solve`, but guessing first the unknown cells with the
+    /// highest weight in `profile`
+    ///
+    /// A good profile, learned on similar puzzles, makes the solver guess the cells
+    /// that unlock the most deductions first, and thus backtrack less. The cells
+    /// are left untouched if the grid is `SolveResult::Unsolvable`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::profile::SolveProfile;
+    /// use picross::solve::SolveResult;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// // Guessing the top right cell first
+    /// let profile = SolveProfile {
+    ///     height: 2,
+    ///     length: 2,
+    ///     weights: vec![vec![0., 1.], vec![0., 0.]],
+    /// };
+    /// assert_eq!(picross.solve_profile_guided(&profile), SolveResult::Solved);
+    /// assert_eq!(picross.cells, vec![vec![Cell::White, Cell::Black],
+    ///                                vec![Cell::Black, Cell::White]]);
+    /// ```
+    ///
+    pub fn solve_profile_guided(&mut self, profile: &SolveProfile) -> SolveResult {
+        let mut picross = self.clone();
+        if picross.backtrack(&mut ProfileHooks(profile)) {
+            *self = picross;
+            SolveResult::Solved
+        } else {
+            SolveResult::Unsolvable
+        }
+    }
+}
@@ -0,0 +1,2529 @@
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use ::{line_blocks, line_runs, Cell, Direction, Picross};
+use analysis::min_span;
+use cache::iter_picross_rows;
+use error::{IterationLimitExceeded, MergeConflict};
+
+/// Error returned when the current cells of a Picross grid cannot be completed into
+/// a valid solution
+#[derive(Clone, Copy, PartialEq, Debug, Error)]
+#[error("The cells contradict the specifications")]
+pub struct Contradiction;
+
+/// Outcome of solving a Picross grid
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SolveResult {
+    /// All the cells were set, and the grid is valid
+    Solved,
+    /// No solution is compatible with the cells the grid had
+    Unsolvable,
+    /// The grid could not be solved without techniques harder than allowed, see
+    /// `Picross::solve_with_difficulty_bound`
+    TooHard,
+}
+
+/// Hardest solving technique allowed by `Picross::solve_with_difficulty_bound`
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Difficulty {
+    /// Line solving only
+    Easy,
+    /// Line solving, and a single guess followed by line solving
+    Medium,
+    /// Line solving and unlimited backtracking
+    Hard,
+}
+
+/// Progress of a single row or column of a Picross grid
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineCompletionStatus {
+    /// The line has no unknown cell and matches its specification
+    Complete,
+    /// The line still has unknown cells; `filled` of its `total` cells are set
+    InProgress { filled: usize, total: usize },
+    /// The line can no longer match its specification
+    Failed,
+    /// The line has unknown cells, and would match its specification if they were
+    /// all white
+    Satisfied,
+}
+
+/// Row or column of a partial solution that can no longer match its
+/// specification, as found by `Picross::validate_with_partial_solution`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ViolationDetail {
+    pub direction: Direction,
+    pub index: usize,
+    /// Blocks of black cells currently in the line, unknown cells being treated as
+    /// white
+    pub found: Vec<usize>,
+}
+
+/// Consistency of a partial solution with the specifications, as returned by
+/// `Picross::validate_with_partial_solution`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PartialValidation {
+    /// The partial solution can be completed into a solution
+    Valid,
+    /// Some lines can no longer match their specification
+    AlreadyViolated { details: Vec<ViolationDetail> },
+    /// Each line can still match its specification on its own, but the lines
+    /// cannot all be completed together
+    Overconstrained,
+}
+
+/// Metrics collected while solving a Picross grid with `benchmark_solver`
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct SolveBenchmark {
+    /// Number of cells set by line solving, including in branches later abandoned
+    pub cells_determined_by_propagation: usize,
+    /// Number of calls to the line solving loop
+    pub propagation_passes: usize,
+    /// Number of guesses made on a cell
+    pub branches_taken: usize,
+    /// Number of guesses that led to a contradiction
+    pub backtracks: usize,
+    /// Wall-clock time spent solving
+    pub total_duration: Duration,
+}
+
+/// Callback of `Picross::propagate_observed`, called with the grid, the line just
+/// solved and the changes it made
+pub(crate) type StepObserver<'a> = dyn FnMut(&Picross, (Direction, usize), &[(usize, Cell)]) + 'a;
+
+/// Returns true if `line[i]` may be black
+fn can_be_black(line: &[Cell], i: usize) -> bool {
+    line[i].value() != Cell::White
+}
+
+/// Returns true if `line[i]` may be white
+fn can_be_white(line: &[Cell], i: usize) -> bool {
+    line[i].value() != Cell::Black
+}
+
+/// Advances `comb`, a strictly increasing sequence of indices below `n`, to the
+/// next one in lexicographic order
+///
+/// Returns false if `comb` was the last one, in which case it is left unspecified.
+fn next_combination(comb: &mut [usize], n: usize) -> bool {
+    let k = comb.len();
+    match (0..k).rev().find(|&i| comb[i] < n - k + i) {
+        None    => false,
+        Some(i) => {
+            comb[i] += 1;
+            let start = comb[i];
+            for (j, c) in comb[i + 1..].iter_mut().enumerate() {
+                *c = start + j + 1;
+            }
+            true
+        }
+    }
+}
+
+/// Computes, with dynamic programming, the value every completion of `line`
+/// following `spec` gives to each cell
+///
+/// Returns a line where forced cells are `Cell::Black` or `Cell::White` and other
+/// cells are `Cell::Unknown`, or `Contradiction` if `line` has no valid completion.
+///
+/// Runs in O(n·k) for a line of n cells and k blocks.
+pub(crate) fn line_dp(line: &[Cell], spec: &[usize]) -> Result<Vec<Cell>, Contradiction> {
+    let n = line.len();
+    let k = spec.len();
+
+    // whites[i]: number of cells known white in line[..i]
+    let mut whites = vec![0; n + 1];
+    for i in 0..n {
+        whites[i + 1] = whites[i] + if can_be_black(line, i) { 0 } else { 1 };
+    }
+    let can_be_block = |s: usize, len: usize| s + len <= n && whites[s + len] == whites[s];
+
+    // fwd[i][j]: line[..i] can hold exactly the blocks spec[..j], the last one
+    // ending at most at i
+    let mut fwd = vec![vec![false; k + 1]; n + 1];
+    fwd[0][0] = true;
+    for i in 1..n + 1 {
+        for j in 0..k + 1 {
+            let white = can_be_white(line, i - 1) && fwd[i - 1][j];
+            let block = j > 0 && i >= spec[j - 1] && can_be_block(i - spec[j - 1], spec[j - 1]) && {
+                let s = i - spec[j - 1];
+                if s == 0 { j == 1 } else { can_be_white(line, s - 1) && fwd[s - 1][j - 1] }
+            };
+            fwd[i][j] = white || block;
+        }
+    }
+
+    // bwd[i][j]: line[i..] can hold exactly the blocks spec[j..], the first one
+    // starting at least at i
+    let mut bwd = vec![vec![false; k + 1]; n + 2];
+    bwd[n][k] = true;
+    for i in (0..n).rev() {
+        for j in 0..k + 1 {
+            let white = can_be_white(line, i) && bwd[i + 1][j];
+            let block = j < k && can_be_block(i, spec[j]) && {
+                let e = i + spec[j];
+                if e == n { j + 1 == k } else { can_be_white(line, e) && bwd[e + 1][j + 1] }
+            };
+            bwd[i][j] = white || block;
+        }
+    }
+
+    if !fwd[n][k] {
+        return Err(Contradiction);
+    }
+
+    // black_cover[i]: difference array counting the valid block placements covering i
+    let mut black_cover = vec![0isize; n + 1];
+    for (j, &len) in spec.iter().enumerate() {
+        for s in 0..n {
+            if !can_be_block(s, len) {
+                continue;
+            }
+            let before = if s == 0 { j == 0 } else { can_be_white(line, s - 1) && fwd[s - 1][j] };
+            let after = if s + len == n { j + 1 == k } else { can_be_white(line, s + len) && bwd[s + len + 1][j + 1] };
+            if before && after {
+                black_cover[s] += 1;
+                black_cover[s + len] -= 1;
+            }
+        }
+    }
+
+    let mut res = Vec::with_capacity(n);
+    let mut cover = 0;
+    for i in 0..n {
+        cover += black_cover[i];
+        let black = cover > 0;
+        let white = can_be_white(line, i) && (0..k + 1).any(|j| fwd[i][j] && bwd[i + 1][j]);
+        res.push(match (black, white) {
+            (true, false) => Cell::Black,
+            (false, true) => Cell::White,
+            _             => Cell::Unknown,
+        });
+    }
+    Ok(res)
+}
+
+/// Returns the only line of size `size` matching `spec`, if its clues leave no
+/// freedom: either there is no clue, or the clues exactly span the line
+fn trivial_line(size: usize, spec: &[usize]) -> Option<Vec<Cell>> {
+    if !spec.is_empty() && min_span(spec) != size {
+        return None;
+    }
+    let mut line = vec![Cell::White; size];
+    let mut pos = 0;
+    for &len in spec {
+        for c in &mut line[pos..pos + len] {
+            *c = Cell::Black;
+        }
+        pos += len + 1;
+    }
+    Some(line)
+}
+
+/// Returns a line of size `size` where the cells forced by the overlap method are
+/// set, other cells being `Cell::Unknown`
+///
+/// Blocks are pushed as far left then as far right as possible: a cell covered by
+/// the same block, or lying in the same gap, in both placements is forced. Returns
+/// `None` if the clues do not fit in the line.
+fn overlap_line(size: usize, spec: &[usize]) -> Option<Vec<Cell>> {
+    if min_span(spec) > size {
+        return None;
+    }
+
+    // Label of each cell in a placement: 2k for the gap before block k, 2k + 1 for
+    // block k
+    let labels = |starts: &[usize]| {
+        let mut res = vec![2 * spec.len(); size];
+        for (k, (&start, &len)) in starts.iter().zip(spec).enumerate().rev() {
+            for l in &mut res[..start + len] {
+                *l = 2 * k;
+            }
+            for l in &mut res[start..start + len] {
+                *l = 2 * k + 1;
+            }
+        }
+        res
+    };
+
+    let mut left = Vec::with_capacity(spec.len());
+    let mut pos = 0;
+    for &len in spec {
+        left.push(pos);
+        pos += len + 1;
+    }
+    let slack = size - min_span(spec);
+    let right = left.iter().map(|s| s + slack).collect::<Vec<usize>>();
+
+    Some(labels(&left).iter().zip(labels(&right).iter()).map(|(l, r)| match (l == r, l % 2) {
+        (false, _) => Cell::Unknown,
+        (true, 0)  => Cell::White,
+        (true, _)  => Cell::Black,
+    }).collect())
+}
+
+/// Sets `line[i]` to `val` if it is unknown, incrementing `changed`
+///
+/// Returns `Contradiction` if `line[i]` already has the other value.
+fn force_cell(line: &mut [Cell], i: usize, val: Cell, changed: &mut usize) -> Result<(), Contradiction> {
+    match line[i].value() {
+        Cell::Unknown => {
+            line[i] = val;
+            *changed += 1;
+            Ok(())
+        }
+        v if v == val => Ok(()),
+        _             => Err(Contradiction),
+    }
+}
+
+/// Sets the cells of `line` forced by a black cell at either end: the first clue
+/// must then start at the first cell, and the last clue end at the last cell
+///
+/// Returns the number of cells that were set, or `Contradiction` if a forced cell
+/// already has the other value, the clue does not fit, or the spec is empty.
+fn force_line_ends(line: &mut [Cell], spec: &[usize]) -> Result<usize, Contradiction> {
+    let n = line.len();
+    if spec.is_empty() && n > 0 && (line[0].value() == Cell::Black || line[n - 1].value() == Cell::Black) {
+        return Err(Contradiction);
+    }
+    let mut changed = 0;
+    let mut force = |line: &mut [Cell], i: usize, val: Cell| force_cell(line, i, val, &mut changed);
+
+    if let (Some(&first), Some(&last)) = (spec.first(), spec.last()) {
+        if n > 0 && line[0].value() == Cell::Black {
+            if first > n {
+                return Err(Contradiction);
+            }
+            for i in 0..first {
+                force(line, i, Cell::Black)?;
+            }
+            if first < n {
+                force(line, first, Cell::White)?;
+            }
+        }
+        if n > 0 && line[n - 1].value() == Cell::Black {
+            if last > n {
+                return Err(Contradiction);
+            }
+            for i in n - last..n {
+                force(line, i, Cell::Black)?;
+            }
+            if last < n {
+                force(line, n - last - 1, Cell::White)?;
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Sets the cells of `line` forced by the first clue of `spec` around the first
+/// black cell
+///
+/// The first clue starts at the earliest position where it fits with only cells
+/// that may be white before it, or later. If the first black cell lies within the
+/// clue placed at that position, the clue must cover it, so the cells from the
+/// black cell to the end of that placement are black. The cells before the
+/// earliest position are white.
+fn glue_first_block(line: &mut [Cell], spec: &[usize], changed: &mut usize) -> Result<(), Contradiction> {
+    let n = line.len();
+    let first = match spec.first() {
+        None         => return Ok(()),
+        Some(&first) => first,
+    };
+    let lo = (0..n + 1).take_while(|&p| p == 0 || can_be_white(line, p - 1))
+        .find(|&p| {
+            p + first <= n
+                && (p..p + first).all(|i| can_be_black(line, i))
+                && (p + first == n || can_be_white(line, p + first))
+        })
+        .ok_or(Contradiction)?;
+
+    for i in 0..lo {
+        force_cell(line, i, Cell::White, changed)?;
+    }
+    if let Some(b) = (lo..lo + first).find(|&i| line[i].value() == Cell::Black) {
+        for i in b..lo + first {
+            force_cell(line, i, Cell::Black, changed)?;
+        }
+        if b == lo && lo + first < n {
+            force_cell(line, lo + first, Cell::White, changed)?;
+        }
+    }
+    Ok(())
+}
+
+/// Applies `glue_first_block` to both ends of `line`
+///
+/// Returns the number of cells that were set, or `Contradiction` if the first or
+/// last clue cannot be placed or a forced cell already has the other value.
+fn glue_line_ends(line: &mut [Cell], spec: &[usize]) -> Result<usize, Contradiction> {
+    let mut changed = 0;
+    glue_first_block(line, spec, &mut changed)?;
+    line.reverse();
+    let reversed_spec = spec.iter().rev().cloned().collect::<Vec<usize>>();
+    let res = glue_first_block(line, &reversed_spec, &mut changed);
+    line.reverse();
+    res.map(|_| changed)
+}
+
+/// Fills the gaps of `line`, that is its maximal runs of cells that may be black,
+/// according to the lengths of the clues of `spec`
+///
+/// A gap shorter than all the clues is white. A gap holding a black cell and no
+/// longer than any clue holds a whole block, and is thus black.
+///
+/// Returns the number of cells that were set, or `Contradiction` if a gap with a
+/// black cell is shorter than all the clues.
+fn fill_line_gaps(line: &mut [Cell], spec: &[usize]) -> Result<usize, Contradiction> {
+    let mut changed = 0;
+    let shortest = spec.iter().cloned().min().unwrap_or(usize::MAX);
+    let may_be_black = line.iter()
+        .map(|c| if c.value() == Cell::White { Cell::White } else { Cell::Black })
+        .collect::<Vec<Cell>>();
+    for (start, len) in line_blocks(&may_be_black) {
+        let has_black = (start..start + len).any(|i| line[i].value() == Cell::Black);
+        let val = if len < shortest {
+            Cell::White
+        } else if has_black && len == shortest {
+            Cell::Black
+        } else {
+            continue;
+        };
+        for i in start..start + len {
+            force_cell(line, i, val, &mut changed)?;
+        }
+    }
+    Ok(changed)
+}
+
+/// Returns the valid starting positions of clue `j` of `spec` in `line` that cover
+/// all of `line[start..end]`
+///
+/// A position is valid if the block only covers cells that may be black, is
+/// bounded by cells that may be white, and leaves enough room for the other clues.
+fn covering_placements(line: &[Cell], spec: &[usize], j: usize, start: usize, end: usize) -> Vec<usize> {
+    let n = line.len();
+    let len = spec[j];
+    if len < end - start {
+        return vec![];
+    }
+    let before = if j == 0 { 0 } else { min_span(&spec[..j]) + 1 };
+    let after = if j + 1 == spec.len() { 0 } else { min_span(&spec[j + 1..]) + 1 };
+
+    (end.saturating_sub(len)..start + 1).filter(|&p| {
+        p >= before && p + len + after <= n
+            && (p..p + len).all(|i| can_be_black(line, i))
+            && (p == 0 || can_be_white(line, p - 1))
+            && (p + len == n || can_be_white(line, p + len))
+    }).collect()
+}
+
+/// Extends the blocks of black cells of `line` that only one clue of `spec` can
+/// cover, bounding them with white cells once they are complete
+///
+/// Returns the number of cells that were set, or `Contradiction` if no clue can
+/// cover some block or a forced cell already has the other value.
+fn complete_line_blocks(line: &mut [Cell], spec: &[usize]) -> Result<usize, Contradiction> {
+    let mut changed = 0;
+    for (start, run) in line_blocks(line) {
+        let end = start + run;
+        let mut candidates = (0..spec.len())
+            .map(|j| (j, covering_placements(line, spec, j, start, end)))
+            .filter(|(_, placements)| !placements.is_empty());
+        let (j, placements) = match (candidates.next(), candidates.next()) {
+            (None, _)          => return Err(Contradiction),
+            (Some(only), None) => only,
+            (Some(_), Some(_)) => continue,
+        };
+
+        // Cells covered by all the placements are black, and if there is only one
+        // placement, the cells around it are white
+        let len = spec[j];
+        let (first, last) = (placements[placements.len() - 1], placements[0]);
+        for i in first..last + len {
+            force_cell(line, i, Cell::Black, &mut changed)?;
+        }
+        if let [p] = placements[..] {
+            if p > 0 {
+                force_cell(line, p - 1, Cell::White, &mut changed)?;
+            }
+            if p + len < line.len() {
+                force_cell(line, p + len, Cell::White, &mut changed)?;
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Hooks customizing the search of `Picross::backtrack`
+///
+/// Each node of the search is line solved by `propagate`, then the cell given by
+/// `choose` is guessed black, then white. `guess` is called before exploring each
+/// guess, and `backtrack` after each explored guess that did not stop the search.
+/// A node without a cell to guess is a solution, passed to `solution`.
+pub(crate) trait SearchHooks {
+    /// Line solves `picross` at the start of a node, a contradiction pruning it
+    fn propagate(&mut self, picross: &mut Picross) -> Result<(), Contradiction> {
+        picross.propagate().map(|_| ())
+    }
+
+    /// Returns the cell to guess in `picross`, or `None` if it is solved
+    fn choose(&mut self, picross: &Picross) -> Option<(usize, usize)> {
+        picross.first_unknown()
+    }
+
+    /// Called before exploring `guess`, whose cell `(y, x)` was set to `c`, and
+    /// returns false to skip it
+    fn guess(&mut self, _guess: &Picross, _y: usize, _x: usize, _c: Cell) -> bool {
+        true
+    }
+
+    /// Called after exploring the guess of `c` for cell `(y, x)`, if it did not
+    /// stop the search
+    fn backtrack(&mut self, _y: usize, _x: usize, _c: Cell) {}
+
+    /// Called on each solution, and returns true to stop the search there
+    fn solution(&mut self, _picross: &Picross) -> bool {
+        true
+    }
+}
+
+/// Hooks of `Picross::benchmark_solver`, recording metrics in `stats` and calling
+/// `on_step` after each step that changed the cells
+struct BenchmarkHooks<'a> {
+    stats: &'a mut SolveBenchmark,
+    on_step: &'a mut dyn FnMut(&Picross),
+}
+
+impl<'a> SearchHooks for BenchmarkHooks<'a> {
+    fn propagate(&mut self, picross: &mut Picross) -> Result<(), Contradiction> {
+        let on_step = &mut self.on_step;
+        self.stats.propagation_passes += 1;
+        self.stats.cells_determined_by_propagation += picross.propagate_observed(&mut |p, _, _| on_step(p))?;
+        Ok(())
+    }
+
+    fn guess(&mut self, guess: &Picross, _y: usize, _x: usize, _c: Cell) -> bool {
+        self.stats.branches_taken += 1;
+        (self.on_step)(guess);
+        true
+    }
+
+    fn backtrack(&mut self, _y: usize, _x: usize, _c: Cell) {
+        self.stats.backtracks += 1;
+    }
+}
+
+/// Hooks of `Picross::solve_with_difficulty_bound`, making at most `depth` nested
+/// guesses if it is not `None`
+struct DepthHooks {
+    depth: Option<usize>,
+    /// Whether a guess was skipped for lack of depth
+    too_hard: bool,
+}
+
+impl SearchHooks for DepthHooks {
+    fn guess(&mut self, _guess: &Picross, _y: usize, _x: usize, _c: Cell) -> bool {
+        if self.depth == Some(0) {
+            self.too_hard = true;
+            return false;
+        }
+        self.depth = self.depth.map(|d| d - 1);
+        true
+    }
+
+    fn backtrack(&mut self, _y: usize, _x: usize, _c: Cell) {
+        self.depth = self.depth.map(|d| d + 1);
+    }
+}
+
+/// Hooks of `Picross::solve_with_iteration_limit`, line solving by passes over
+/// all the lines and decrementing `passes` for each one
+struct PassHooks {
+    passes: usize,
+    /// Whether a pass was needed after `passes` ran out
+    exhausted: bool,
+}
+
+impl SearchHooks for PassHooks {
+    fn propagate(&mut self, picross: &mut Picross) -> Result<(), Contradiction> {
+        loop {
+            if self.passes == 0 {
+                self.exhausted = true;
+                return Err(Contradiction);
+            }
+            self.passes -= 1;
+
+            let mut changed = false;
+            let lines = (0..picross.height).map(|y| (Direction::Row, y))
+                .chain((0..picross.length).map(|x| (Direction::Col, x)))
+                .collect::<Vec<(Direction, usize)>>();
+            for (dir, idx) in lines {
+                changed |= !picross.apply_line_constraint(dir, idx)?.is_empty();
+            }
+            if !changed || picross.first_unknown().is_none() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn guess(&mut self, _guess: &Picross, _y: usize, _x: usize, _c: Cell) -> bool {
+        !self.exhausted
+    }
+}
+
+/// Hooks of `Picross::steps_to_solve`, counting in `steps` the line solving steps
+/// that changed some cells and the guesses on the path to the current node
+struct StepHooks {
+    steps: usize,
+    /// Value of `steps` before each guess on the path
+    saved: Vec<usize>,
+}
+
+impl SearchHooks for StepHooks {
+    fn propagate(&mut self, picross: &mut Picross) -> Result<(), Contradiction> {
+        let steps = &mut self.steps;
+        picross.propagate_observed(&mut |_, _, _| *steps += 1).map(|_| ())
+    }
+
+    fn guess(&mut self, _guess: &Picross, _y: usize, _x: usize, _c: Cell) -> bool {
+        self.saved.push(self.steps);
+        self.steps += 1;
+        true
+    }
+
+    fn backtrack(&mut self, _y: usize, _x: usize, _c: Cell) {
+        self.steps = self.saved.pop().expect("Backtracking from a guess that was made");
+    }
+}
+
+/// Hooks of `Picross::count_solutions`, counting the solutions until `limit` is
+/// reached
+struct CountHooks {
+    limit: usize,
+    count: usize,
+}
+
+impl SearchHooks for CountHooks {
+    fn guess(&mut self, _guess: &Picross, _y: usize, _x: usize, _c: Cell) -> bool {
+        self.count < self.limit
+    }
+
+    fn solution(&mut self, _picross: &Picross) -> bool {
+        self.count += 1;
+        false
+    }
+}
+
+impl Picross {
+    /// Returns the coordinates of the first unknown cell, if any
+    pub(crate) fn first_unknown(&self) -> Option<(usize, usize)> {
+        (0..self.height).flat_map(|y| (0..self.length).map(move |x| (y, x)))
+                        .find(|&(y, x)| self.cells[y][x] == Cell::Unknown)
+    }
+
+    /// Solves the grid by line solving and backtracking, as customized by `hooks`
+    ///
+    /// Returns true if `hooks` stopped the search on a solution, in which case the
+    /// cells are set to it.
+    pub(crate) fn backtrack<H: SearchHooks>(&mut self, hooks: &mut H) -> bool {
+        if hooks.propagate(self).is_err() {
+            return false;
+        }
+
+        let (y, x) = match hooks.choose(self) {
+            None    => return hooks.solution(self),
+            Some(p) => p,
+        };
+        for &c in &[Cell::Black, Cell::White] {
+            let mut guess = self.clone();
+            guess.cells[y][x] = c;
+            if !hooks.guess(&guess, y, x, c) {
+                continue;
+            }
+            if guess.backtrack(hooks) {
+                *self = guess;
+                return true;
+            }
+            hooks.backtrack(y, x, c);
+        }
+        false
+    }
+
+    /// Solves the grid by line solving and backtracking, recording metrics in
+    /// `stats` and calling `on_step` after each step that changed the cells
+    ///
+    /// Returns true if a solution was found, in which case the cells are set to it.
+    fn search(&mut self, stats: &mut SolveBenchmark, on_step: &mut dyn FnMut(&Picross)) -> bool {
+        self.backtrack(&mut BenchmarkHooks { stats, on_step })
+    }
+
+    ///
+    /// Solves the grid, starting from the current cells
+    ///
+    /// Line solving is applied until no more progress is made, then a guess is
+    /// made on an unknown cell, backtracking on contradictions. If the grid has
+    /// several solutions, one of them is chosen.
+    ///
+    /// The cells are left untouched if the grid is `SolveResult::Unsolvable`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::solve::SolveResult;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.solve(), SolveResult::Solved);
+    /// assert!(picross.is_valid());
+    ///
+    /// picross.row_spec = vec![vec![2], vec![2]];
+    /// assert_eq!(picross.solve(), SolveResult::Unsolvable);
+    /// ```
+    ///
+    pub fn solve(&mut self) -> SolveResult {
+        self.benchmark_solver_result().0
+    }
+
+    ///
+    /// Solves the grid like `solve`, and returns true if a valid solution was found
+    ///
+    /// This is intended for tests, where it replaces solving then checking the
+    /// result with `is_valid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the solver claims to have solved the grid but the result is not
+    /// valid, which is a bug in the solver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![1]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// assert!(picross.auto_solve_and_verify());
+    ///
+    /// picross.cells = vec![vec![Cell::Unknown; 2]; 2];
+    /// picross.col_spec = vec![vec![2], vec![2]];
+    /// assert!(!picross.auto_solve_and_verify());
+    /// ```
+    ///
+    pub fn auto_solve_and_verify(&mut self) -> bool {
+        match self.solve() {
+            SolveResult::Solved => {
+                assert!(self.is_valid(), "The solver returned an invalid grid:\n{}", self);
+                true
+            }
+            SolveResult::Unsolvable | SolveResult::TooHard => false,
+        }
+    }
+
+    ///
+    /// Solves the grid like `solve`, without using techniques harder than
+    /// `max_difficulty`
+    ///
+    /// `Difficulty::Easy` only allows line solving, `Difficulty::Medium` also allows
+    /// guessing a single cell then line solving, and `Difficulty::Hard` allows
+    /// unlimited backtracking. Returns `SolveResult::TooHard` if the grid could not
+    /// be solved within these bounds, and `SolveResult::Unsolvable` only if it was
+    /// proven to have no solution. The cells are left untouched unless the grid is
+    /// `SolveResult::Solved`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::solve::{Difficulty, SolveResult};
+    ///
+    /// // Line solving is enough for this grid
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![1]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// assert_eq!(picross.solve_with_difficulty_bound(Difficulty::Easy), SolveResult::Solved);
+    ///
+    /// // But this one needs a guess
+    /// picross.cells = vec![vec![Cell::Unknown; 2]; 2];
+    /// picross.row_spec = vec![vec![1], vec![1]];
+    /// picross.col_spec = vec![vec![1], vec![1]];
+    /// assert_eq!(picross.solve_with_difficulty_bound(Difficulty::Easy), SolveResult::TooHard);
+    /// assert_eq!(picross.cells, vec![vec![Cell::Unknown; 2]; 2]);
+    /// assert_eq!(picross.solve_with_difficulty_bound(Difficulty::Medium), SolveResult::Solved);
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    pub fn solve_with_difficulty_bound(&mut self, max_difficulty: Difficulty) -> SolveResult {
+        let depth = match max_difficulty {
+            Difficulty::Easy   => Some(0),
+            Difficulty::Medium => Some(1),
+            Difficulty::Hard   => None,
+        };
+
+        let mut picross = self.clone();
+        let mut hooks = DepthHooks { depth, too_hard: false };
+        if picross.backtrack(&mut hooks) {
+            *self = picross;
+            SolveResult::Solved
+        } else if hooks.too_hard {
+            SolveResult::TooHard
+        } else {
+            SolveResult::Unsolvable
+        }
+    }
+
+    ///
+    /// Solves the grid like `solve`, failing if more than `max_iter` passes over
+    /// all the lines are needed
+    ///
+    /// Each pass solves every row then every column once. Passes are made until
+    /// the grid is filled or a pass changes no cell, then a guess is made if
+    /// needed, and the passes made while exploring guesses count too. Unlike a
+    /// timeout, this limit gives the same result on every platform. The cells are
+    /// left untouched unless the grid is `SolveResult::Solved`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::error::IterationLimitExceeded;
+    /// use picross::solve::SolveResult;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![1]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.solve_with_iteration_limit(0), Err(IterationLimitExceeded(0)));
+    /// assert_eq!(picross.cells, vec![vec![Cell::Unknown; 2]; 2]);
+    /// assert_eq!(picross.solve_with_iteration_limit(1), Ok(SolveResult::Solved));
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    pub fn solve_with_iteration_limit(&mut self, max_iter: usize)
+            -> Result<SolveResult, IterationLimitExceeded> {
+        let mut picross = self.clone();
+        let mut hooks = PassHooks { passes: max_iter, exhausted: false };
+        if picross.backtrack(&mut hooks) {
+            *self = picross;
+            Ok(SolveResult::Solved)
+        } else if hooks.exhausted {
+            Err(IterationLimitExceeded(max_iter))
+        } else {
+            Ok(SolveResult::Unsolvable)
+        }
+    }
+
+    ///
+    /// Returns the number of moves the solver makes to solve the grid from scratch,
+    /// or `None` if the grid has no solution
+    ///
+    /// A move is either a line solving step that determines some cells, or a guess.
+    /// Only the moves leading to the solution are counted, not the ones made in
+    /// branches that ended in a contradiction. This is the length of the path found
+    /// by the solver, which is not necessarily the shortest one, but makes a good
+    /// difficulty metric. The current cells are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// // Solving both rows is enough
+    /// assert_eq!(picross.steps_to_solve(), Some(2));
+    ///
+    /// // A guess, then one line for each of the other cells
+    /// picross.row_spec = vec![vec![1], vec![1]];
+    /// assert_eq!(picross.steps_to_solve(), Some(4));
+    ///
+    /// picross.row_spec = vec![vec![2], vec![2]];
+    /// assert_eq!(picross.steps_to_solve(), None);
+    /// ```
+    ///
+    pub fn steps_to_solve(&self) -> Option<usize> {
+        let mut picross = self.clone();
+        picross.cells = vec![vec![Cell::Unknown; self.length]; self.height];
+        let mut hooks = StepHooks { steps: 0, saved: vec![] };
+        if picross.backtrack(&mut hooks) {
+            Some(hooks.steps)
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Solves the grid like `solve`, and returns metrics about the resolution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let bench = picross.benchmark_solver();
+    /// assert!(picross.is_valid());
+    /// assert_eq!(bench.branches_taken, 1);
+    /// assert_eq!(bench.backtracks, 0);
+    /// assert_eq!(bench.propagation_passes, 2);
+    /// assert_eq!(bench.cells_determined_by_propagation, 3);
+    /// ```
+    ///
+    pub fn benchmark_solver(&mut self) -> SolveBenchmark {
+        self.benchmark_solver_result().1
+    }
+
+    /// Sets to white the unknown cells of the rows and columns without clues,
+    /// returning the number of cells set
+    fn fill_all_white_lines(&mut self) -> usize {
+        let mut total = 0;
+        for y in self.all_white_rows() {
+            for c in self.cells[y].iter_mut().filter(|c| **c == Cell::Unknown) {
+                *c = Cell::White;
+                total += 1;
+            }
+        }
+        for x in self.all_white_cols() {
+            for row in self.cells.iter_mut().filter(|r| r[x] == Cell::Unknown) {
+                row[x] = Cell::White;
+                total += 1;
+            }
+        }
+        total
+    }
+
+    /// Solves the grid, returning both the outcome and the metrics
+    fn benchmark_solver_result(&mut self) -> (SolveResult, SolveBenchmark) {
+        let start = Instant::now();
+        let mut stats = SolveBenchmark::default();
+
+        let mut picross = self.clone();
+        stats.cells_determined_by_propagation += picross.fill_all_white_lines();
+        let res = if picross.search(&mut stats, &mut |_| {}) {
+            *self = picross;
+            SolveResult::Solved
+        } else {
+            SolveResult::Unsolvable
+        };
+
+        stats.total_duration = start.elapsed();
+        (res, stats)
+    }
+
+    ///
+    /// Sets all the cells of row `row` that are forced by its specification and its
+    /// current cells, using dynamic programming
+    ///
+    /// Returns the number of cells that were set, or `Contradiction` if the row can
+    /// no longer match its specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::solve::Contradiction;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 1,
+    ///     length: 6,
+    ///     cells: vec![vec![Cell::Unknown, Cell::Unknown, Cell::Black,
+    ///                      Cell::Unknown, Cell::Unknown, Cell::White]],
+    ///     row_spec: vec![vec![1, 2]],
+    ///     col_spec: vec![vec![]; 6],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.solve_row_dp(0), Ok(4));
+    /// assert_eq!(picross.cells[0], vec![Cell::Black, Cell::White, Cell::Black,
+    ///                                   Cell::Black, Cell::White, Cell::White]);
+    ///
+    /// picross.row_spec[0] = vec![3];
+    /// assert_eq!(picross.solve_row_dp(0), Err(Contradiction));
+    /// ```
+    ///
+    pub fn solve_row_dp(&mut self, row: usize) -> Result<usize, Contradiction> {
+        self.apply_line_constraint(Direction::Row, row).map(|changes| changes.len())
+    }
+
+    ///
+    /// Sets the unknown cells of row `row` that are forced by the overlap method,
+    /// and returns how many were set
+    ///
+    /// The blocks are pushed as far left then as far right as possible, and the
+    /// cells that get the same value in both placements are set. The current cells
+    /// are not taken into account, which makes this much faster but less powerful
+    /// than `solve_row_dp`: a solver can try it first, and fall back to
+    /// `solve_row_dp` when it makes no progress. Known cells are never changed, and
+    /// nothing is set if the clues do not fit in the row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 6,
+    ///     cells: vec![vec![Unknown; 6], vec![Black, Unknown, Unknown, Unknown, Unknown, Unknown]],
+    ///     row_spec: vec![vec![3, 1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1], vec![1], vec![], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.solve_row_greedy(0), 2);
+    /// assert_eq!(picross.cells[0], vec![Unknown, Black, Black, Unknown, Unknown, Unknown]);
+    ///
+    /// // The overlap method does not use the known cells, unlike solve_row_dp
+    /// assert_eq!(picross.solve_row_greedy(1), 0);
+    /// assert_eq!(picross.solve_row_dp(1), Ok(5));
+    /// assert_eq!(picross.cells[1], vec![Black, White, White, White, White, White]);
+    /// ```
+    ///
+    pub fn solve_row_greedy(&mut self, row: usize) -> usize {
+        let forced = match overlap_line(self.length, &self.row_spec[row]) {
+            Some(line) => line,
+            None       => return 0,
+        };
+
+        let mut changed = 0;
+        for (c, f) in self.cells[row].iter_mut().zip(forced) {
+            if *c == Cell::Unknown && f != Cell::Unknown {
+                *c = f;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    ///
+    /// Sets the cells of row `row` forced by a black cell at either of its ends
+    ///
+    /// If the first cell is black, the first clue starts there: its cells are black
+    /// and the cell following it is white. The same goes for the last cell and the
+    /// last clue. The overlap method of `solve_row_greedy` does not find these
+    /// cells, which are often the first ones found by hand.
+    ///
+    /// Returns the number of cells that were set, or `Contradiction` if one of these
+    /// cells already had the other value or if an end cell is black in an empty row.
+    /// Cells are set up to the contradiction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    /// use picross::solve::Contradiction;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 6,
+    ///     cells: vec![vec![Black, Unknown, Unknown, Unknown, Unknown, Black],
+    ///                 vec![Black, White, Unknown, Unknown, Unknown, Unknown]],
+    ///     row_spec: vec![vec![2, 1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1], vec![], vec![], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.force_row_ends(0), Ok(3));
+    /// assert_eq!(picross.cells[0], vec![Black, Black, White, Unknown, White, Black]);
+    /// assert_eq!(picross.force_row_ends(1), Err(Contradiction));
+    /// assert_eq!(picross.force_col_ends(0), Ok(0));
+    ///
+    /// // A black end cell is a contradiction in an empty line
+    /// picross.col_spec[5] = vec![];
+    /// assert_eq!(picross.force_col_ends(5), Err(Contradiction));
+    /// ```
+    ///
+    pub fn force_row_ends(&mut self, row: usize) -> Result<usize, Contradiction> {
+        force_line_ends(&mut self.cells[row], &self.row_spec[row])
+    }
+
+    ///
+    /// Sets the cells of column `col` forced by a black cell at either of its ends
+    ///
+    /// See `force_row_ends` for details and an example.
+    ///
+    pub fn force_col_ends(&mut self, col: usize) -> Result<usize, Contradiction> {
+        let mut line = self.get_line(Direction::Col, col);
+        let res = force_line_ends(&mut line, &self.col_spec[col]);
+        self.set_line(Direction::Col, col, line);
+        res
+    }
+
+    ///
+    /// Sets the cells of all the lines forced by their first and last clues, using
+    /// the edge techniques
+    ///
+    /// The first clue of a line cannot start before the first position where it
+    /// fits after cells that may be white, which are then white. If a black cell
+    /// lies within the clue placed at that position, the clue is glued to it: the
+    /// cells from the black cell to the end of that placement are black, and if the
+    /// black cell is the first cell the clue can cover, the clue is entirely placed
+    /// and followed by a white cell. The same goes for the last clue from the other
+    /// end. This generalizes `force_row_ends` to edges made of white cells.
+    ///
+    /// Lines are handled once, rows first, each benefiting from the cells set
+    /// before it. Lines that contradict are left untouched.
+    ///
+    /// Returns the number of cells set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 6,
+    ///     cells: vec![vec![White, Black, Unknown, Unknown, Unknown, Unknown],
+    ///                 vec![Unknown, Unknown, Black, Unknown, Unknown, Unknown]],
+    ///     row_spec: vec![vec![3, 1], vec![4]],
+    ///     col_spec: vec![vec![], vec![2], vec![2], vec![2], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.solve_edge_constraints() > 0);
+    /// assert_eq!(picross.cells[0], vec![White, Black, Black, Black, White, Unknown]);
+    /// assert_eq!(picross.cells[1], vec![Unknown, Black, Black, Black, Unknown, Unknown]);
+    /// ```
+    ///
+    pub fn solve_edge_constraints(&mut self) -> usize {
+        let mut total = 0;
+        let lines = (0..self.height).map(|y| (Direction::Row, y))
+            .chain((0..self.length).map(|x| (Direction::Col, x)))
+            .collect::<Vec<_>>();
+        for (dir, idx) in lines {
+            let mut line = self.get_line(dir, idx);
+            if let Ok(changed) = glue_line_ends(&mut line, self.line_spec(dir, idx)) {
+                self.set_line(dir, idx, line);
+                total += changed;
+            }
+        }
+        total
+    }
+
+    ///
+    /// Sets the cells of all the lines determined by the length of their gaps, using
+    /// the gap analysis technique
+    ///
+    /// A gap is a maximal run of cells of a line that are not white. A gap too
+    /// short for any clue of the line is white. A gap holding a black cell must hold
+    /// a whole block, so it is black if it is exactly as long as the shortest clue.
+    /// These cells are often missed by the overlap method of `solve_row_greedy`.
+    ///
+    /// Lines are handled once, rows first, each benefiting from the cells set
+    /// before it. Lines that contradict are left untouched.
+    ///
+    /// Returns the number of cells set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 1,
+    ///     length: 8,
+    ///     cells: vec![vec![Unknown, White, Unknown, Black, White, Unknown, Unknown, Unknown]],
+    ///     row_spec: vec![vec![2, 3]],
+    ///     col_spec: vec![vec![], vec![], vec![1], vec![1], vec![], vec![1], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.solve_gap_filling(), 2);
+    /// assert_eq!(picross.cells[0],
+    ///            vec![White, White, Black, Black, White, Unknown, Unknown, Unknown]);
+    /// ```
+    ///
+    pub fn solve_gap_filling(&mut self) -> usize {
+        let mut total = 0;
+        let lines = (0..self.height).map(|y| (Direction::Row, y))
+            .chain((0..self.length).map(|x| (Direction::Col, x)))
+            .collect::<Vec<_>>();
+        for (dir, idx) in lines {
+            let mut line = self.get_line(dir, idx);
+            if let Ok(changed) = fill_line_gaps(&mut line, self.line_spec(dir, idx)) {
+                self.set_line(dir, idx, line);
+                total += changed;
+            }
+        }
+        total
+    }
+
+    ///
+    /// Extends the blocks of black cells of row `row` that only one clue can cover
+    ///
+    /// For each block of adjacent black cells, the clues that can cover it are
+    /// listed, taking into account the known cells around it and the room left for
+    /// the other clues. If only one clue can, the cells covered by all its possible
+    /// positions are set black, and if it has only one possible position, the cells
+    /// around it are set white.
+    ///
+    /// Returns the number of cells that were set, or `Contradiction` if no clue can
+    /// cover some block. Cells are set up to the contradiction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    /// use picross::solve::Contradiction;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 7,
+    ///     cells: vec![vec![Unknown, Unknown, Black, Black, Unknown, Unknown, Unknown],
+    ///                 vec![Unknown, Black, Black, Black, Unknown, Unknown, Unknown]],
+    ///     row_spec: vec![vec![1, 3], vec![2, 1]],
+    ///     col_spec: vec![vec![], vec![1], vec![2], vec![2], vec![1], vec![], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// // Only the 3 can cover the two black cells, and it cannot start before the
+    /// // third cell to leave room for the 1
+    /// assert_eq!(picross.solve_row_block_completion(0), Ok(3));
+    /// assert_eq!(picross.cells[0], vec![Unknown, White, Black, Black, Black, White, Unknown]);
+    ///
+    /// assert_eq!(picross.solve_row_block_completion(1), Err(Contradiction));
+    /// ```
+    ///
+    pub fn solve_row_block_completion(&mut self, row: usize) -> Result<usize, Contradiction> {
+        complete_line_blocks(&mut self.cells[row], &self.row_spec[row])
+    }
+
+    ///
+    /// Checks whether the current cells of row `row` can still be completed to match
+    /// its specification, using dynamic programming
+    ///
+    /// Runs in O(n·k) for a row of n cells and k clues.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 4,
+    ///     cells: vec![vec![Unknown, White, Unknown, Unknown],
+    ///                 vec![Black, Unknown, Black, Unknown]],
+    ///     row_spec: vec![vec![2], vec![2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.check_row_feasibility_dp(0));
+    /// assert!(!picross.check_row_feasibility_dp(1));
+    /// assert!(picross.check_col_feasibility_dp(0));
+    /// ```
+    ///
+    #[doc(alias = "is_partial_row_feasible")]
+    pub fn check_row_feasibility_dp(&self, row: usize) -> bool {
+        line_dp(&self.cells[row], &self.row_spec[row]).is_ok()
+    }
+
+    ///
+    /// Checks whether the current cells of column `col` can still be completed to
+    /// match its specification, using dynamic programming
+    ///
+    /// See `check_row_feasibility_dp` for details.
+    ///
+    pub fn check_col_feasibility_dp(&self, col: usize) -> bool {
+        line_dp(&self.get_line(Direction::Col, col), &self.col_spec[col]).is_ok()
+    }
+
+    ///
+    /// Checks whether row `row` can be completed with no block crossing the boundary
+    /// between columns `col - 1` and `col`
+    ///
+    /// This holds if one of the two cells around the boundary can be white, and the
+    /// clues can be split into a left part that fits in cells `0..col` and a right
+    /// part that fits in cells `col..length` with that cell white, given the current
+    /// cells. Each part is checked like `check_row_feasibility_dp`. Splitting at
+    /// either end of the row is the same as the row being feasible. A row that
+    /// cannot be split anywhere a block could otherwise end lets the solver prune
+    /// guesses that overlap line solving would not reject.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `col` is greater than the length of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]],
+    ///     row_spec: vec![vec![2, 2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.row_spec_can_split_at(0, 0));
+    /// assert!(!picross.row_spec_can_split_at(0, 1));
+    /// assert!(picross.row_spec_can_split_at(0, 2));
+    /// assert!(picross.row_spec_can_split_at(0, 3));
+    ///
+    /// let tight = Picross {
+    ///     height: 1,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]],
+    ///     row_spec: vec![vec![1, 1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// assert!(!tight.row_spec_can_split_at(0, 1));
+    /// ```
+    ///
+    pub fn row_spec_can_split_at(&self, row: usize, col: usize) -> bool {
+        let line = &self.cells[row];
+        let spec = &self.row_spec[row];
+        if col == 0 || col == line.len() {
+            return line_dp(line, spec).is_ok();
+        }
+
+        [col - 1, col].iter().filter(|&&b| can_be_white(line, b)).any(|&b| {
+            let mut line = line.clone();
+            line[b] = Cell::White;
+            let (left, right) = line.split_at(col);
+            (0..spec.len() + 1).any(|k| {
+                line_dp(left, &spec[..k]).is_ok() && line_dp(right, &spec[k..]).is_ok()
+            })
+        })
+    }
+
+    ///
+    /// Checks whether the specification of row `row` and its current cells alone
+    /// determine all its remaining unknown cells, without looking at the columns
+    ///
+    /// Returns false if the row cannot be completed to match its specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 4,
+    ///     cells: vec![vec![Unknown, Black, Unknown, Unknown],
+    ///                 vec![Unknown; 4],
+    ///                 vec![Unknown, White, Unknown, Unknown]],
+    ///     row_spec: vec![vec![1], vec![1], vec![3]],
+    ///     col_spec: vec![vec![], vec![2], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.row_can_be_determined_without_cols(0));
+    /// assert!(!picross.row_can_be_determined_without_cols(1));
+    /// assert!(!picross.row_can_be_determined_without_cols(2));
+    /// ```
+    ///
+    pub fn row_can_be_determined_without_cols(&self, row: usize) -> bool {
+        match line_dp(&self.cells[row], &self.row_spec[row]) {
+            Ok(forced) => forced.iter().all(|c| *c != Cell::Unknown),
+            Err(_)     => false,
+        }
+    }
+
+    ///
+    /// Returns the unknown cells that solving each row and each column once would
+    /// set, as `(row, col, value)` triples in row-major order, without changing the
+    /// grid
+    ///
+    /// Each line is solved from the current cells only, without taking into account
+    /// the cells the other lines would set. Lines that can no longer match their
+    /// specification are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Unknown; 3]; 2],
+    ///     row_spec: vec![vec![3], vec![1]],
+    ///     col_spec: vec![vec![1], vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.detect_forcing_cells(),
+    ///            vec![(0, 0, Black), (0, 1, Black), (0, 2, Black), (1, 1, Black)]);
+    /// assert_eq!(picross.cells, vec![vec![Unknown; 3]; 2]);
+    /// ```
+    ///
+    pub fn detect_forcing_cells(&self) -> Vec<(usize, usize, Cell)> {
+        let mut forced = vec![vec![Cell::Unknown; self.length]; self.height];
+        let lines = (0..self.height).map(|y| (Direction::Row, y))
+            .chain((0..self.length).map(|x| (Direction::Col, x)));
+        for (dir, idx) in lines {
+            let line = self.get_line(dir, idx);
+            if let Ok(line_forced) = line_dp(&line, self.line_spec(dir, idx)) {
+                for (i, (c, f)) in line.iter().zip(line_forced).enumerate() {
+                    if *c == Cell::Unknown && f != Cell::Unknown {
+                        let (y, x) = match dir {
+                            Direction::Row => (idx, i),
+                            Direction::Col => (i, idx),
+                        };
+                        forced[y][x] = f;
+                    }
+                }
+            }
+        }
+
+        let mut res = vec![];
+        for (y, row) in forced.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                if c != Cell::Unknown {
+                    res.push((y, x, c));
+                }
+            }
+        }
+        res
+    }
+
+    ///
+    /// Returns the unknown cells that line solving sets, as `(row, col)` pairs in
+    /// the order they are set
+    ///
+    /// No guess is made, so cells that line solving alone cannot determine are not
+    /// returned. If line solving ends in a contradiction, only the cells set before
+    /// it are returned. The grid is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![1]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.cell_solve_order(), vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+    /// ```
+    ///
+    pub fn cell_solve_order(&self) -> Vec<(usize, usize)> {
+        let mut order = vec![];
+        let _ = self.clone().propagate_observed(&mut |_, (dir, idx), changes| {
+            order.extend(changes.iter().map(|&(i, _)| match dir {
+                Direction::Row => (idx, i),
+                Direction::Col => (i, idx),
+            }));
+        });
+        order
+    }
+
+    ///
+    /// Checks whether the cells of `partial`, which may be unknown, can still be
+    /// completed into a solution of the specifications of `self`
+    ///
+    /// Each row and column of `partial` is first checked on its own: the known
+    /// cells must be compatible with the specification, and leave room for the
+    /// missing blocks. Then the whole grid is solved to check the lines can be
+    /// completed together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `partial` do not have the same dimensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    /// use picross::Cell::{Black, White, Unknown};
+    /// use picross::solve::{PartialValidation, ViolationDetail};
+    ///
+    /// let puzzle = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let mut partial = puzzle.clone();
+    /// partial.cells = vec![vec![Black, Unknown], vec![Unknown, Unknown]];
+    /// assert_eq!(puzzle.validate_with_partial_solution(&partial), PartialValidation::Valid);
+    ///
+    /// partial.cells = vec![vec![Black, Black], vec![Unknown, Unknown]];
+    /// assert_eq!(puzzle.validate_with_partial_solution(&partial),
+    ///            PartialValidation::AlreadyViolated { details: vec![
+    ///                ViolationDetail { direction: Direction::Row, index: 0, found: vec![2] },
+    ///            ] });
+    ///
+    /// // Each line is fine on its own, but the black cell must be on the diagonal
+    /// partial.cells = vec![vec![Black, Unknown], vec![Unknown, White]];
+    /// assert_eq!(puzzle.validate_with_partial_solution(&partial),
+    ///            PartialValidation::Overconstrained);
+    /// ```
+    ///
+    pub fn validate_with_partial_solution(&self, partial: &Picross) -> PartialValidation {
+        if self.height != partial.height || self.length != partial.length {
+            panic!("Cannot validate a partial solution of different dimensions!");
+        }
+
+        let mut board = self.clone();
+        board.cells = partial.cells.clone();
+
+        let lines = (0..self.height).map(|y| (Direction::Row, y))
+            .chain((0..self.length).map(|x| (Direction::Col, x)));
+        let details = lines.filter_map(|(direction, index)| {
+            let line = board.get_line(direction, index);
+            match line_dp(&line, board.line_spec(direction, index)) {
+                Ok(_)  => None,
+                Err(_) => Some(ViolationDetail { direction, index, found: line_runs(&line) }),
+            }
+        }).collect::<Vec<ViolationDetail>>();
+
+        if !details.is_empty() {
+            PartialValidation::AlreadyViolated { details }
+        } else if board.solve() == SolveResult::Unsolvable {
+            PartialValidation::Overconstrained
+        } else {
+            PartialValidation::Valid
+        }
+    }
+
+    ///
+    /// Sets all the cells of row or column `idx` that are forced by its
+    /// specification and its current cells
+    ///
+    /// Returns the list of the changes, as `(position_in_line, new_cell_value)`
+    /// pairs, so that only the perpendicular lines going through the changed cells
+    /// need to be solved again. Returns `Contradiction` if the line can no longer
+    /// match its specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 3,
+    ///     length: 1,
+    ///     cells: vec![vec![Cell::Unknown], vec![Cell::Unknown], vec![Cell::White]],
+    ///     row_spec: vec![vec![1], vec![1], vec![]],
+    ///     col_spec: vec![vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(
+    ///     picross.apply_line_constraint(Direction::Col, 0),
+    ///     Ok(vec![(0, Cell::Black), (1, Cell::Black)])
+    /// );
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    pub fn apply_line_constraint(&mut self, dir: Direction, idx: usize)
+            -> Result<Vec<(usize, Cell)>, Contradiction> {
+        let mut line = self.get_line(dir, idx);
+        let forced = line_dp(&line, self.line_spec(dir, idx))?;
+
+        let mut changes = vec![];
+        for (i, (c, f)) in line.iter_mut().zip(forced).enumerate() {
+            if *c == Cell::Unknown && f != Cell::Unknown {
+                *c = f;
+                changes.push((i, f));
+            }
+        }
+        if !changes.is_empty() {
+            self.set_line(dir, idx, line);
+        }
+        Ok(changes)
+    }
+
+    ///
+    /// Returns the progress of row `row` towards matching its specification
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    /// use picross::solve::LineCompletionStatus;
+    ///
+    /// let picross = Picross {
+    ///     height: 4,
+    ///     length: 3,
+    ///     cells: vec![vec![Black, White, Black],
+    ///                 vec![Black, Unknown, Unknown],
+    ///                 vec![Unknown, Black, Unknown],
+    ///                 vec![White, White, Unknown]],
+    ///     row_spec: vec![vec![1, 1], vec![1], vec![2], vec![2]],
+    ///     col_spec: vec![vec![], vec![], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.row_completion_status(0), LineCompletionStatus::Complete);
+    /// assert_eq!(picross.row_completion_status(1), LineCompletionStatus::Satisfied);
+    /// assert_eq!(picross.row_completion_status(2),
+    ///            LineCompletionStatus::InProgress { filled: 1, total: 3 });
+    /// assert_eq!(picross.row_completion_status(3), LineCompletionStatus::Failed);
+    /// ```
+    ///
+    pub fn row_completion_status(&self, row: usize) -> LineCompletionStatus {
+        let line = &self.cells[row];
+        let spec = &self.row_spec[row];
+        let filled = line.iter().filter(|c| **c != Cell::Unknown).count();
+
+        if line_dp(line, spec).is_err() {
+            LineCompletionStatus::Failed
+        } else if filled == line.len() {
+            LineCompletionStatus::Complete
+        } else if line_runs(line) == *spec {
+            LineCompletionStatus::Satisfied
+        } else {
+            LineCompletionStatus::InProgress { filled, total: line.len() }
+        }
+    }
+
+    ///
+    /// Checks whether the `clue_index`-th clue of row or column `idx` can start at
+    /// position `clue_offset`, given the current cells
+    ///
+    /// The clue must fit in the line, cover no white cell, and not be directly
+    /// preceded nor followed by a black cell. The other clues are not taken into
+    /// account: this is the local check that line solvers build upon.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` or `clue_index` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown, Cell::Unknown, Cell::White,
+    ///                      Cell::Unknown, Cell::Black]],
+    ///     row_spec: vec![vec![2]],
+    ///     col_spec: vec![vec![], vec![], vec![], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.can_place_clue_at(Direction::Row, 0, 0, 0));
+    /// assert!(!picross.can_place_clue_at(Direction::Row, 0, 1, 0)); // covers a white cell
+    /// assert!(!picross.can_place_clue_at(Direction::Row, 0, 2, 0)); // followed by a black cell
+    /// assert!(picross.can_place_clue_at(Direction::Row, 0, 3, 0));
+    /// assert!(!picross.can_place_clue_at(Direction::Row, 0, 4, 0)); // does not fit
+    /// ```
+    ///
+    pub fn can_place_clue_at(&self, dir: Direction, idx: usize, clue_offset: usize,
+                             clue_index: usize) -> bool {
+        let line = self.get_line(dir, idx);
+        let len = self.line_spec(dir, idx)[clue_index];
+        let end = clue_offset + len;
+
+        end <= line.len()
+            && (clue_offset..end).all(|i| can_be_black(&line, i))
+            && (clue_offset == 0 || can_be_white(&line, clue_offset - 1))
+            && (end == line.len() || can_be_white(&line, end))
+    }
+
+    ///
+    /// Builds row or column `idx` from the starting positions of its clues,
+    /// `true` standing for black cells
+    ///
+    /// `placement[i]` is the position of the first cell of the `i`-th clue. The
+    /// placement is not checked to match the specification: overlapping or
+    /// touching blocks are drawn as is. The current cells are not taken into
+    /// account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placement` does not have one position per clue, or if a block
+    /// does not fit in the line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 5,
+    ///     cells: vec![vec![Cell::Unknown; 5]; 2],
+    ///     row_spec: vec![vec![2, 1], vec![]],
+    ///     col_spec: vec![vec![1], vec![1], vec![], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.spec_to_bitmask(Direction::Row, 0, &[0, 3]),
+    ///            vec![true, true, false, true, false]);
+    /// assert_eq!(picross.spec_to_bitmask(Direction::Col, 1, &[1]), vec![false, true]);
+    /// ```
+    ///
+    pub fn spec_to_bitmask(&self, dir: Direction, idx: usize, placement: &[usize]) -> Vec<bool> {
+        let size = match dir {
+            Direction::Row => self.length,
+            Direction::Col => self.height,
+        };
+        let spec = self.line_spec(dir, idx);
+        if placement.len() != spec.len() {
+            panic!("Expected {} clue positions but got {}!", spec.len(), placement.len());
+        }
+
+        let mut mask = vec![false; size];
+        for (&start, &len) in placement.iter().zip(spec) {
+            for m in &mut mask[start..start + len] {
+                *m = true;
+            }
+        }
+        mask
+    }
+
+    ///
+    /// Returns an iterator over all the complete rows or columns `idx` that match
+    /// both the specification and the current cells
+    ///
+    /// The lines are generated lazily, so that iteration can be stopped early, for
+    /// instance as soon as two placements have been found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell, Direction};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 4,
+    ///     cells: vec![vec![Cell::Unknown, Cell::Unknown, Cell::Unknown, Cell::White]],
+    ///     row_spec: vec![vec![1, 1]],
+    ///     col_spec: vec![vec![1], vec![], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(
+    ///     picross.all_placements_for_line(Direction::Row, 0).collect::<Vec<_>>(),
+    ///     vec![vec![Black, White, Black, White]]
+    /// );
+    /// assert_eq!(picross.all_placements_for_line(Direction::Col, 1).count(), 1);
+    /// assert_eq!(picross.all_placements_for_line(Direction::Col, 0).take(2).count(), 1);
+    /// ```
+    ///
+    pub fn all_placements_for_line<'a>(&'a self, dir: Direction, idx: usize)
+            -> impl Iterator<Item=Vec<Cell>> + 'a {
+        let line = self.get_line(dir, idx);
+        iter_picross_rows(line.len(), self.line_spec(dir, idx)).filter(move |p| {
+            line.iter().zip(p.iter()).all(|(l, c)| *l == Cell::Unknown || l.value() == *c)
+        })
+    }
+
+    ///
+    /// Sets all the cells that can be deduced by looking at one line at a time,
+    /// until no more progress is made
+    ///
+    /// All the lines are solved once, then only the lines crossing a cell that
+    /// changed are solved again.
+    ///
+    /// Returns the number of cells that were set, or `Contradiction` if some row or
+    /// column can no longer match its specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown; 3]; 2],
+    ///     row_spec: vec![vec![3], vec![1]],
+    ///     col_spec: vec![vec![1], vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.propagate(), Ok(6));
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::solve::Contradiction;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 1,
+    ///     length: 3,
+    ///     cells: vec![vec![Cell::Unknown, Cell::White, Cell::Unknown]],
+    ///     row_spec: vec![vec![2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.propagate(), Err(Contradiction));
+    /// ```
+    ///
+    pub fn propagate(&mut self) -> Result<usize, Contradiction> {
+        self.propagate_observed(&mut |_, _, _| {})
+    }
+
+    ///
+    /// Enforces singleton arc consistency on the grid, a stronger form of arc
+    /// consistency (AC-3) than line solving
+    ///
+    /// Each cell is a variable of domain `{Black, White}`, and each row and column
+    /// specification is a constraint over the cells of its line. Plain arc
+    /// consistency removes a value from a domain when no assignment of a single line
+    /// uses it, which is exactly what `propagate` computes. Here, a value is also
+    /// removed when setting the cell to it and propagating through all the lines
+    /// leads to a contradiction, which takes the dependencies between lines into
+    /// account. Cells are probed again until a fixpoint is reached.
+    ///
+    /// Returns the number of cells whose domain was reduced to a single value, or
+    /// `Contradiction` if some domain became empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 4,
+    ///     length: 4,
+    ///     cells: vec![vec![Cell::Unknown; 4]; 4],
+    ///     row_spec: vec![vec![2], vec![2], vec![1, 1], vec![1]],
+    ///     col_spec: vec![vec![2], vec![1], vec![2], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// // Line solving alone finds nothing
+    /// assert_eq!(picross.clone().propagate(), Ok(0));
+    ///
+    /// assert_eq!(picross.solve_arc_consistency(), Ok(16));
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    pub fn solve_arc_consistency(&mut self) -> Result<usize, Contradiction> {
+        let unknown = self.count_unknown();
+        self.propagate()?;
+        loop {
+            let mut changed = false;
+            for y in 0..self.height {
+                for x in 0..self.length {
+                    if self.cells[y][x] != Cell::Unknown {
+                        continue;
+                    }
+                    let admits = |c: Cell| self.probe(&[(y, x, c)]).is_ok();
+                    let c = match (admits(Cell::Black), admits(Cell::White)) {
+                        (false, false) => return Err(Contradiction),
+                        (true, false)  => Cell::Black,
+                        (false, true)  => Cell::White,
+                        (true, true)   => continue,
+                    };
+                    *self = self.probe(&[(y, x, c)])?;
+                    changed = true;
+                }
+            }
+            if !changed {
+                return Ok(unknown - self.count_unknown());
+            }
+        }
+    }
+
+    /// Returns the number of unknown cells
+    fn count_unknown(&self) -> usize {
+        self.cells.iter().flatten().filter(|c| **c == Cell::Unknown).count()
+    }
+
+    /// Returns a copy of the grid where the cells of `cells`, given as `(row, col,
+    /// value)`, are set and line solving is applied, or `Contradiction` if it fails
+    ///
+    /// The grid must already be line solved, as only the lines going through these
+    /// cells are solved at first. The number of cells set by line solving is not
+    /// returned.
+    fn probe(&self, cells: &[(usize, usize, Cell)]) -> Result<Picross, Contradiction> {
+        let mut probe = self.clone();
+        let mut lines = vec![];
+        for &(y, x, c) in cells {
+            probe.cells[y][x] = c;
+            lines.push((Direction::Row, y));
+            lines.push((Direction::Col, x));
+        }
+        probe.propagate_lines_observed(lines, &mut |_, _, _| {})?;
+        Ok(probe)
+    }
+
+    /// Propagates like `propagate`, calling `on_step` after each line solving that
+    /// changed some cells, with the line solved and the changes it made
+    pub(crate) fn propagate_observed(&mut self,
+                                     on_step: &mut StepObserver)
+            -> Result<usize, Contradiction> {
+        let lines = (0..self.height).map(|y| (Direction::Row, y))
+            .chain((0..self.length).map(|x| (Direction::Col, x)))
+            .collect();
+        self.propagate_lines_observed(lines, on_step)
+    }
+
+    /// Propagates like `propagate_observed`, but starting with the lines of `lines`
+    /// only
+    ///
+    /// The other lines are only solved once one of their cells changes, so they
+    /// must already be line solved for the result to be the one of `propagate`.
+    fn propagate_lines_observed(&mut self,
+                                lines: Vec<(Direction, usize)>,
+                                on_step: &mut StepObserver)
+            -> Result<usize, Contradiction> {
+        let mut queue = VecDeque::new();
+        let mut queued = HashSet::new();
+        for line in lines {
+            if queued.insert(line) {
+                queue.push_back(line);
+            }
+        }
+
+        let mut total = 0;
+        while let Some((dir, idx)) = queue.pop_front() {
+            queued.remove(&(dir, idx));
+
+            let changes = self.apply_line_constraint(dir, idx)?;
+            total += changes.len();
+            if !changes.is_empty() {
+                on_step(self, (dir, idx), &changes);
+            }
+
+            let cross = match dir {
+                Direction::Row => Direction::Col,
+                Direction::Col => Direction::Row,
+            };
+            for (i, _) in changes {
+                if queued.insert((cross, i)) {
+                    queue.push_back((cross, i));
+                }
+            }
+        }
+        Ok(total)
+    }
+
+    ///
+    /// Sets the unknown cells of the rows and columns whose clues alone determine
+    /// all their cells
+    ///
+    /// These are the lines without clues, which are all white, and the lines whose
+    /// clues exactly span them, like `[5]` or `[2,2]` in a line of 5 cells. This is a
+    /// fast first pass before running the full line solver. Known cells are left
+    /// untouched, even if they disagree with the clues.
+    ///
+    /// Returns the number of cells set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 3,
+    ///     length: 3,
+    ///     cells: vec![vec![Unknown; 3]; 3],
+    ///     row_spec: vec![vec![1, 1], vec![], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1], vec![2]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.solve_trivial_lines(), 6);
+    /// assert_eq!(picross.cells, vec![vec![Black, White, Black],
+    ///                                vec![White, White, White],
+    ///                                vec![Unknown, Unknown, Unknown]]);
+    /// ```
+    ///
+    pub fn solve_trivial_lines(&mut self) -> usize {
+        let lines = (0..self.height).map(|y| (Direction::Row, y))
+            .chain((0..self.length).map(|x| (Direction::Col, x)))
+            .collect::<Vec<(Direction, usize)>>();
+
+        let mut total = 0;
+        for (dir, idx) in lines {
+            let size = match dir {
+                Direction::Row => self.length,
+                Direction::Col => self.height,
+            };
+            if let Some(forced) = trivial_line(size, self.line_spec(dir, idx)) {
+                let mut line = self.get_line(dir, idx);
+                for (c, f) in line.iter_mut().zip(forced) {
+                    if *c == Cell::Unknown {
+                        *c = f;
+                        total += 1;
+                    }
+                }
+                self.set_line(dir, idx, line);
+            }
+        }
+        total
+    }
+
+    /// Returns true if setting cell `(y, x)` to `val` keeps both its row and its
+    /// column solvable on their own
+    fn admits(&self, y: usize, x: usize, val: Cell) -> bool {
+        let mut row = self.cells[y].clone();
+        row[x] = val;
+        let mut col = self.get_line(Direction::Col, x);
+        col[y] = val;
+        line_dp(&row, &self.row_spec[y]).is_ok() && line_dp(&col, &self.col_spec[x]).is_ok()
+    }
+
+    ///
+    /// Checks whether cell `(row, col)` must be black, given the current cells
+    ///
+    /// A known cell is forced to its value. An unknown cell is forced black if
+    /// making it white immediately contradicts its row or its column, without
+    /// looking at the rest of the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(row, col)` is outside of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 3,
+    ///     cells: vec![vec![Unknown, Unknown, White]],
+    ///     row_spec: vec![vec![2]],
+    ///     col_spec: vec![vec![1], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(picross.cell_is_forced_black(0, 0));
+    /// assert!(!picross.cell_is_forced_white(0, 0));
+    /// assert!(picross.cell_is_forced_white(0, 2));
+    /// ```
+    ///
+    pub fn cell_is_forced_black(&self, row: usize, col: usize) -> bool {
+        match self.cells[row][col].value() {
+            Cell::Unknown => !self.admits(row, col, Cell::White),
+            v             => v == Cell::Black,
+        }
+    }
+
+    ///
+    /// Checks whether cell `(row, col)` must be white, given the current cells
+    ///
+    /// See `cell_is_forced_black` for details.
+    ///
+    pub fn cell_is_forced_white(&self, row: usize, col: usize) -> bool {
+        match self.cells[row][col].value() {
+            Cell::Unknown => !self.admits(row, col, Cell::Black),
+            v             => v == Cell::White,
+        }
+    }
+
+    ///
+    /// Sets the unknown cells for which one of the two values immediately
+    /// contradicts their row or their column, using the "naked singles" technique
+    ///
+    /// Each unknown cell is tried both black and white, and is set to the other
+    /// value if exactly one of them contradicts. Cells are scanned once, row by
+    /// row, each cell benefiting from the ones set before it. Cells for which both
+    /// values contradict are left unknown.
+    ///
+    /// Returns the number of cells set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Black, Unknown, Unknown],
+    ///                 vec![Unknown, Unknown, Unknown]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.solve_naked_singles(), 5);
+    /// assert_eq!(picross.cells, vec![vec![Black, White, White],
+    ///                                vec![White, Black, White]]);
+    /// ```
+    ///
+    pub fn solve_naked_singles(&mut self) -> usize {
+        let mut total = 0;
+        for y in 0..self.height {
+            for x in 0..self.length {
+                if self.cells[y][x] != Cell::Unknown {
+                    continue;
+                }
+                match (self.cell_is_forced_black(y, x), self.cell_is_forced_white(y, x)) {
+                    (true, false) => self.cells[y][x] = Cell::Black,
+                    (false, true) => self.cells[y][x] = Cell::White,
+                    _ => continue,
+                }
+                total += 1;
+            }
+        }
+        total
+    }
+
+    /// Returns the values each cell of `positions` in line `idx` of direction `dir`
+    /// takes in the joint assignments of these cells that `probe` does not
+    /// contradict, as `(can be black, can be white)`
+    ///
+    /// Each assignment is tried right before its complement. Assignments that only
+    /// give values already known to be possible are skipped, and so are the ones
+    /// the line alone rejects, before probing the whole grid.
+    fn tuple_values(&self, dir: Direction, idx: usize, positions: &[usize]) -> Vec<(bool, bool)> {
+        let line = self.get_line(dir, idx);
+        let mut values = vec![(false, false); positions.len()];
+        let mask = (1usize << positions.len()) - 1;
+        let assignments = (0..1usize << positions.len() >> 1).flat_map(|b| vec![b, !b & mask]);
+        for bits in assignments {
+            let val = |j: usize| if bits >> j & 1 == 1 { Cell::Black } else { Cell::White };
+            let known = values.iter().enumerate().all(|(j, v)| match val(j) {
+                Cell::Black => v.0,
+                _           => v.1,
+            });
+            if known {
+                continue;
+            }
+
+            let mut assigned = line.clone();
+            for (j, &i) in positions.iter().enumerate() {
+                assigned[i] = val(j);
+            }
+            if line_dp(&assigned, self.line_spec(dir, idx)).is_err() {
+                continue;
+            }
+            let cells = positions.iter().enumerate().map(|(j, &i)| match dir {
+                Direction::Row => (idx, i, val(j)),
+                Direction::Col => (i, idx, val(j)),
+            }).collect::<Vec<_>>();
+            if self.probe(&cells).is_ok() {
+                for (j, v) in values.iter_mut().enumerate() {
+                    match val(j) {
+                        Cell::Black => v.0 = true,
+                        _           => v.1 = true,
+                    }
+                }
+            }
+        }
+        values
+    }
+
+    /// Applies `solve_arc_consistency`, then sets the unknown cells that take the
+    /// same value in all the assignments of `k` unknown cells of a line that
+    /// `tuple_values` allows, until a fixpoint is reached
+    ///
+    /// Tuples containing a cell set in the meantime are skipped, and the grid is
+    /// made arc consistent again after each line that changed.
+    fn solve_naked_tuples(&mut self, k: usize) -> Result<usize, Contradiction> {
+        let unknown_before = self.count_unknown();
+        self.solve_arc_consistency()?;
+        let lines = (0..self.height).map(|y| (Direction::Row, y))
+            .chain((0..self.length).map(|x| (Direction::Col, x)))
+            .collect::<Vec<_>>();
+        loop {
+            let mut changed = false;
+            for &(dir, idx) in &lines {
+                let unknown = self.get_line(dir, idx).iter().enumerate()
+                    .filter(|&(_, c)| *c == Cell::Unknown)
+                    .map(|(i, _)| i)
+                    .collect::<Vec<usize>>();
+                if unknown.len() < k {
+                    continue;
+                }
+
+                let mut line = self.get_line(dir, idx);
+                let mut comb = (0..k).collect::<Vec<usize>>();
+                loop {
+                    let positions = comb.iter().map(|&c| unknown[c]).collect::<Vec<usize>>();
+                    if positions.iter().all(|&i| line[i] == Cell::Unknown) {
+                        for (&i, v) in positions.iter().zip(self.tuple_values(dir, idx, &positions)) {
+                            line[i] = match v {
+                                (false, false) => return Err(Contradiction),
+                                (true, false)  => Cell::Black,
+                                (false, true)  => Cell::White,
+                                (true, true)   => continue,
+                            };
+                            changed = true;
+                        }
+                    }
+                    if !next_combination(&mut comb, unknown.len()) {
+                        break;
+                    }
+                }
+
+                if line != self.get_line(dir, idx) {
+                    self.set_line(dir, idx, line);
+                    self.solve_arc_consistency()?;
+                }
+            }
+            if !changed {
+                return Ok(unknown_before - self.count_unknown());
+            }
+        }
+    }
+
+    ///
+    /// Sets the unknown cells determined by the "naked pairs" technique, on top of
+    /// `solve_arc_consistency`
+    ///
+    /// The grid is first made arc consistent, so that every unknown cell can take
+    /// both values as far as setting it alone and line solving the grid tell. Then,
+    /// for each pair of unknown cells of a line, the four joint assignments of the
+    /// two cells are probed the same way, and a cell that takes the same value in
+    /// all the assignments that do not contradict is set to it. This catches cells
+    /// whose two values are each fine on their own, but lead to contradictions
+    /// together with any value of the other cell. The grid is made arc consistent
+    /// again after each line that changed, until a fixpoint is reached.
+    ///
+    /// Each pass probes up to four assignments per pair of unknown cells of each
+    /// line, that is about 2·n² probes per line of n unknown cells, each one line
+    /// solving the grid from the lines of the pair. This makes it much slower than
+    /// `solve_arc_consistency` on large grids with many unknown cells.
+    ///
+    /// Returns the number of cells set, or `Contradiction` if the grid has no
+    /// solution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 7,
+    ///     length: 3,
+    ///     cells: vec![vec![Unknown; 3]; 7],
+    ///     row_spec: vec![vec![1], vec![1], vec![], vec![1], vec![], vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![2], vec![1, 1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// // Arc consistency only finds the empty rows and the white cell between them
+    /// let mut arc = picross.clone();
+    /// assert_eq!(arc.solve_arc_consistency(), Ok(7));
+    /// assert_eq!(arc.cells[3], vec![Unknown, White, Unknown]);
+    ///
+    /// assert_eq!(picross.solve_naked_pairs(), Ok(9));
+    /// assert_eq!(picross.cells[3], vec![White, White, Black]);
+    /// ```
+    ///
+    pub fn solve_naked_pairs(&mut self) -> Result<usize, Contradiction> {
+        self.solve_naked_tuples(2)
+    }
+
+    ///
+    /// Sets the unknown cells determined by the "naked triples" technique, on top
+    /// of `solve_arc_consistency`
+    ///
+    /// Works like `solve_naked_pairs`, probing the eight joint assignments of each
+    /// triple of unknown cells of a line instead. Each pass thus probes about
+    /// 4·n³/3 assignments per line of n unknown cells, which is only practical on
+    /// small grids or lines with few unknown cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{White, Unknown};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 9,
+    ///     length: 5,
+    ///     cells: vec![vec![Unknown; 5]; 9],
+    ///     row_spec: vec![vec![1, 1], vec![1], vec![1], vec![1], vec![1, 1],
+    ///                    vec![1], vec![1, 1], vec![1, 1], vec![1]],
+    ///     col_spec: vec![vec![2], vec![2], vec![2, 1, 1], vec![1, 1], vec![1, 1, 1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.clone().solve_arc_consistency(), Ok(2));
+    /// assert_eq!(picross.clone().solve_naked_pairs(), Ok(2));
+    ///
+    /// assert_eq!(picross.solve_naked_triples(), Ok(5));
+    /// assert_eq!(picross.cells[0][0], White);
+    /// assert_eq!(picross.cells[1], vec![White, Unknown, Unknown, White, White]);
+    /// assert_eq!(picross.cells[2][2], White);
+    /// ```
+    ///
+    pub fn solve_naked_triples(&mut self) -> Result<usize, Contradiction> {
+        self.solve_naked_tuples(3)
+    }
+
+    ///
+    /// Solves the grid like `solve`, and returns snapshots of the cells taken every
+    /// `n` steps of the solver
+    ///
+    /// A step is either a line solving that changed some cells, or a guess. The
+    /// snapshots start with the initial cells and end with the solved cells, or
+    /// with the last cells explored if the grid is unsolvable. The specifications
+    /// of all the snapshots are the ones of `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![2], vec![1]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let snapshots = picross.solve_with_checkpoints(1);
+    /// assert_eq!(snapshots, vec![
+    ///     vec![vec![Unknown, Unknown], vec![Unknown, Unknown]],
+    ///     vec![vec![Black, Black], vec![Unknown, Unknown]],
+    ///     vec![vec![Black, Black], vec![Black, Unknown]],
+    ///     vec![vec![Black, Black], vec![Black, White]],
+    /// ]);
+    /// assert!(picross.is_valid());
+    /// ```
+    ///
+    pub fn solve_with_checkpoints(&mut self, n: usize) -> Vec<Vec<Vec<Cell>>> {
+        if n == 0 {
+            panic!("Cannot take checkpoints every 0 steps!");
+        }
+
+        let mut snapshots = vec![self.cells.clone()];
+        let mut last = self.cells.clone();
+        let mut steps = 0;
+
+        let mut picross = self.clone();
+        let solved = picross.search(&mut SolveBenchmark::default(), &mut |p| {
+            steps += 1;
+            last = p.cells.clone();
+            if steps % n == 0 {
+                snapshots.push(p.cells.clone());
+            }
+        });
+        if solved {
+            *self = picross;
+        }
+
+        if snapshots.last() != Some(&last) {
+            snapshots.push(last);
+        }
+        snapshots
+    }
+
+    ///
+    /// Counts the solutions of the Picross grid that are compatible with the
+    /// current cells, stopping as soon as `limit` solutions have been found
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.count_solutions(10), 2);
+    /// assert_eq!(picross.count_solutions(1), 1);
+    /// ```
+    ///
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut hooks = CountHooks { limit, count: 0 };
+        self.clone().backtrack(&mut hooks);
+        hooks.count
+    }
+
+    ///
+    /// Returns true if exactly one solution is compatible with the current cells
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Cell::Unknown; 2]; 2],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert!(!picross.has_unique_solution());
+    ///
+    /// picross.cells[0][0] = Cell::Black;
+    /// assert!(picross.has_unique_solution());
+    /// ```
+    ///
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    ///
+    /// Merges the cells of two partial solutions of the same grid
+    ///
+    /// Each cell known in either `self` or `other` is known in the result. This is
+    /// useful to combine the results of solvers run in parallel. Returns a
+    /// `MergeConflict` on the first cell that is black in one and white in the
+    /// other.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` do not have the same specifications.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown};
+    /// use picross::error::MergeConflict;
+    ///
+    /// let left = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Black, Unknown], vec![Unknown, Unknown]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// let mut right = left.clone();
+    /// right.cells = vec![vec![Black, White], vec![Unknown, Black]];
+    ///
+    /// let merged = left.merge_partial_solutions(&right).unwrap();
+    /// assert_eq!(merged.cells, vec![vec![Black, White], vec![Unknown, Black]]);
+    ///
+    /// right.cells[0][0] = White;
+    /// assert_eq!(left.merge_partial_solutions(&right).err(), Some(MergeConflict { row: 0, col: 0 }));
+    /// ```
+    ///
+    pub fn merge_partial_solutions(&self, other: &Picross) -> Result<Picross, MergeConflict> {
+        if self.row_spec != other.row_spec || self.col_spec != other.col_spec {
+            panic!("Cannot merge solutions of grids with different specifications!");
+        }
+
+        let mut res = self.clone();
+        for (y, (row, other_row)) in res.cells.iter_mut().zip(&other.cells).enumerate() {
+            for (x, (c, o)) in row.iter_mut().zip(other_row).enumerate() {
+                match (c.value(), o.value()) {
+                    (Cell::Unknown, _) => *c = *o,
+                    (_, Cell::Unknown) => {}
+                    (a, b) if a != b   => return Err(MergeConflict { row: y, col: x }),
+                    _                  => {}
+                }
+            }
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::BufRead;
+
+use ::{Cell, Picross};
+use parse::ParseError;
+
+///
+/// Iterator lazily parsing a sequence of Picross grids from a reader
+///
+/// The grids are in the format expected by `Picross::parse`, one after the other,
+/// with one item per line. Blank lines are ignored. Only one grid is read per call
+/// to `next`, and the iteration stops after the first error.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use picross::stream::PicrossStreamParser;
+///
+/// let data = "1\n2\n[2]\n[1]\n[1]\n\n2\n1\n[]\n[1]\n[1]\n";
+/// let mut parser = PicrossStreamParser::new(Cursor::new(data));
+///
+/// let first = parser.next().unwrap().unwrap();
+/// assert_eq!(first.row_spec, vec![vec![2]]);
+/// assert_eq!(first.col_spec, vec![vec![1], vec![1]]);
+///
+/// let second = parser.next().unwrap().unwrap();
+/// assert_eq!(second.row_spec, vec![vec![], vec![1]]);
+///
+/// assert!(parser.next().is_none());
+/// ```
+///
+/// ```
+/// use std::io::Cursor;
+/// use picross::stream::PicrossStreamParser;
+///
+/// let mut parser = PicrossStreamParser::new(Cursor::new("2\n2\n[1]\n"));
+/// assert!(parser.next().unwrap().is_err());
+/// assert!(parser.next().is_none());
+///
+/// let corrupt = "1000000000000000000\n2\n[1]\n";
+/// assert!(PicrossStreamParser::new(Cursor::new(corrupt)).next().unwrap().is_err());
+/// ```
+///
+pub struct PicrossStreamParser<R: BufRead> {
+    reader: R,
+    failed: bool,
+}
+
+impl<R: BufRead> PicrossStreamParser<R> {
+    /// Creates a parser reading grids from `reader`
+    pub fn new(reader: R) -> PicrossStreamParser<R> {
+        PicrossStreamParser {
+            reader,
+            failed: false,
+        }
+    }
+
+    /// Returns the next non-blank line, or `None` at the end of the input
+    fn next_line(&mut self) -> Result<Option<String>, ParseError> {
+        loop {
+            let mut line = String::new();
+            let read = self.reader.read_line(&mut line)
+                .map_err(|e| ParseError(format!("Failed to read input: {}", e)))?;
+            if read == 0 {
+                return Ok(None);
+            }
+            let line = line.trim();
+            if !line.is_empty() {
+                return Ok(Some(line.to_string()));
+            }
+        }
+    }
+
+    /// Returns the next non-blank line, failing at the end of the input
+    fn expect_line(&mut self, what: &str) -> Result<String, ParseError> {
+        self.next_line()?.ok_or_else(|| ParseError(format!("Expected to find {}!", what)))
+    }
+
+    /// Parses the rest of a grid whose height line was `height`
+    fn parse_grid(&mut self, height: &str) -> Result<Picross, ParseError> {
+        let height = height.parse::<usize>()
+            .map_err(|_| ParseError("Expected integer height!".to_string()))?;
+        let length = self.expect_line("a length")?.parse::<usize>()
+            .map_err(|_| ParseError("Expected integer length!".to_string()))?;
+
+        let mut row_spec = Vec::new();
+        for _ in 0..height {
+            row_spec.push(Picross::try_get_specs(self.expect_line("a row specification")?)?);
+        }
+        let mut col_spec = Vec::new();
+        for _ in 0..length {
+            col_spec.push(Picross::try_get_specs(self.expect_line("a column specification")?)?);
+        }
+
+        Ok(Picross {
+            height,
+            length,
+            row_spec,
+            col_spec,
+            possible_rows: vec![],
+            possible_cols: vec![],
+            cells: vec![vec![Cell::Unknown; length]; height],
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for PicrossStreamParser<R> {
+    type Item = Result<Picross, ParseError>;
+
+    fn next(&mut self) -> Option<Result<Picross, ParseError>> {
+        if self.failed {
+            return None;
+        }
+
+        let res = match self.next_line() {
+            Ok(None)             => return None,
+            Ok(Some(ref height)) => self.parse_grid(height),
+            Err(e)               => Err(e),
+        };
+        self.failed = res.is_err();
+        Some(res)
+    }
+}
@@ -0,0 +1,418 @@
+use ::{line_runs, Cell, Picross};
+
+/// Symmetry class of a Picross grid, as returned by `Picross::symmetry_type`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SymmetryType {
+    /// The grid has none of the symmetries below
+    None,
+    /// The grid is unchanged by a 180° rotation
+    RotationalHalf,
+    /// The grid is unchanged by a 90° rotation
+    RotationalFull,
+    /// The grid is unchanged by swapping its left and right sides
+    HorizontalReflection,
+    /// The grid is unchanged by swapping its top and bottom sides
+    VerticalReflection,
+    /// The grid is unchanged by a reflection across its main diagonal
+    DiagonalReflection,
+    /// The grid has all the symmetries above
+    AllSymmetries,
+}
+
+/// Panics if `perm` is not a permutation of `0..size`
+fn check_permutation(perm: &[usize], size: usize) {
+    let mut seen = vec![false; size];
+    for &i in perm {
+        if i >= size || seen[i] {
+            panic!("Expected a permutation of 0..{}, but got {:?}!", size, perm);
+        }
+        seen[i] = true;
+    }
+    if perm.len() != size {
+        panic!("Expected a permutation of 0..{}, but got {:?}!", size, perm);
+    }
+}
+
+impl Picross {
+    /// Builds the grid made of `cells`, `row_spec` and `col_spec`
+    fn with_parts(&self, cells: Vec<Vec<Cell>>, row_spec: Vec<Vec<usize>>,
+                  col_spec: Vec<Vec<usize>>) -> Picross {
+        Picross {
+            height: row_spec.len(),
+            length: col_spec.len(),
+            cells,
+            row_spec,
+            col_spec,
+            possible_rows: vec![],
+            possible_cols: vec![],
+        }
+    }
+
+    /// Returns true if `self` and `other` have the same cells and specifications
+    fn same_grid(&self, other: &Picross) -> bool {
+        self.cells == other.cells && self.row_spec == other.row_spec
+            && self.col_spec == other.col_spec
+    }
+
+    ///
+    /// Returns the grid rotated by 90° clockwise, along with its specifications
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Black, Black, White],
+    ///                 vec![White, Black, White]],
+    ///     row_spec: vec![vec![2], vec![1]],
+    ///     col_spec: vec![vec![1], vec![2], vec![]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let rotated = picross.rotate_90();
+    /// assert_eq!(rotated.height, 3);
+    /// assert_eq!(rotated.cells, vec![vec![White, Black],
+    ///                                vec![Black, Black],
+    ///                                vec![White, White]]);
+    /// assert!(rotated.is_valid());
+    /// ```
+    ///
+    pub fn rotate_90(&self) -> Picross {
+        let cells = (0..self.length).map(|x| {
+            (0..self.height).rev().map(|y| self.cells[y][x]).collect()
+        }).collect();
+        let row_spec = self.col_spec.iter()
+                                    .map(|s| s.iter().rev().cloned().collect())
+                                    .collect();
+        let col_spec = self.row_spec.iter().rev().cloned().collect();
+        self.with_parts(cells, row_spec, col_spec)
+    }
+
+    ///
+    /// Returns the grid rotated by 180°, along with its specifications
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 3,
+    ///     cells: vec![vec![Black, White, Black]],
+    ///     row_spec: vec![vec![1, 1]],
+    ///     col_spec: vec![vec![1], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// assert_eq!(picross.rotate_180().cells, picross.cells);
+    /// ```
+    ///
+    pub fn rotate_180(&self) -> Picross {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    ///
+    /// Returns the grid with its left and right sides swapped, along with its
+    /// specifications
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let picross = Picross {
+    ///     height: 1,
+    ///     length: 4,
+    ///     cells: vec![vec![Black, White, Black, Black]],
+    ///     row_spec: vec![vec![1, 2]],
+    ///     col_spec: vec![vec![1], vec![], vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let flipped = picross.flip_horizontal();
+    /// assert_eq!(flipped.cells, vec![vec![Black, Black, White, Black]]);
+    /// assert_eq!(flipped.row_spec, vec![vec![2, 1]]);
+    /// assert!(flipped.is_valid());
+    /// ```
+    ///
+    pub fn flip_horizontal(&self) -> Picross {
+        let cells = self.cells.iter().map(|r| r.iter().rev().cloned().collect()).collect();
+        let row_spec = self.row_spec.iter().map(|s| s.iter().rev().cloned().collect()).collect();
+        let col_spec = self.col_spec.iter().rev().cloned().collect();
+        self.with_parts(cells, row_spec, col_spec)
+    }
+
+    ///
+    /// Returns the grid with its top and bottom sides swapped, along with its
+    /// specifications
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 1,
+    ///     cells: vec![vec![Black], vec![White], vec![White]],
+    ///     row_spec: vec![vec![1], vec![], vec![]],
+    ///     col_spec: vec![vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let flipped = picross.flip_vertical();
+    /// assert_eq!(flipped.cells, vec![vec![White], vec![White], vec![Black]]);
+    /// assert_eq!(flipped.row_spec, vec![vec![], vec![], vec![1]]);
+    /// assert!(flipped.is_valid());
+    /// ```
+    ///
+    pub fn flip_vertical(&self) -> Picross {
+        let cells = self.cells.iter().rev().cloned().collect();
+        let row_spec = self.row_spec.iter().rev().cloned().collect();
+        let col_spec = self.col_spec.iter().map(|s| s.iter().rev().cloned().collect()).collect();
+        self.with_parts(cells, row_spec, col_spec)
+    }
+
+    ///
+    /// Returns the grid whose row `i` is row `perm[i]` of `self`, row
+    /// specifications included
+    ///
+    /// The column specifications are left unchanged: they generally no longer match
+    /// the cells, and puzzle editors reordering rows have to update them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `perm` is not a permutation of `0..height`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let picross = Picross {
+    ///     height: 3,
+    ///     length: 1,
+    ///     cells: vec![vec![Black], vec![White], vec![Black]],
+    ///     row_spec: vec![vec![1], vec![], vec![1]],
+    ///     col_spec: vec![vec![1, 1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let permuted = picross.apply_permutation_to_rows(&[1, 0, 2]);
+    /// assert_eq!(permuted.cells, vec![vec![White], vec![Black], vec![Black]]);
+    /// assert_eq!(permuted.row_spec, vec![vec![], vec![1], vec![1]]);
+    /// assert_eq!(permuted.col_spec, picross.col_spec);
+    /// ```
+    ///
+    pub fn apply_permutation_to_rows(&self, perm: &[usize]) -> Picross {
+        check_permutation(perm, self.height);
+        let cells = perm.iter().map(|&y| self.cells[y].clone()).collect();
+        let row_spec = perm.iter().map(|&y| self.row_spec[y].clone()).collect();
+        self.with_parts(cells, row_spec, self.col_spec.clone())
+    }
+
+    ///
+    /// Returns the grid whose column `i` is column `perm[i]` of `self`, column
+    /// specifications included
+    ///
+    /// See `apply_permutation_to_rows` for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `perm` is not a permutation of `0..length`.
+    ///
+    pub fn apply_permutation_to_cols(&self, perm: &[usize]) -> Picross {
+        check_permutation(perm, self.length);
+        let cells = self.cells.iter()
+                              .map(|r| perm.iter().map(|&x| r[x]).collect())
+                              .collect();
+        let col_spec = perm.iter().map(|&x| self.col_spec[x].clone()).collect();
+        self.with_parts(cells, self.row_spec.clone(), col_spec)
+    }
+
+    ///
+    /// Swaps black and white cells, hints included, leaving unknown cells as is
+    ///
+    /// The specifications are left untouched, see `invert_specs` and `inverted`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White, Unknown, Hint};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 1,
+    ///     length: 4,
+    ///     cells: vec![vec![Black, White, Unknown, Hint(true)]],
+    ///     row_spec: vec![vec![1, 1]],
+    ///     col_spec: vec![vec![1], vec![], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// picross.invert_cells();
+    /// assert_eq!(picross.cells, vec![vec![White, Black, Unknown, Hint(false)]]);
+    /// ```
+    ///
+    pub fn invert_cells(&mut self) {
+        for c in self.cells.iter_mut().flat_map(|r| r.iter_mut()) {
+            *c = match *c {
+                Cell::Black   => Cell::White,
+                Cell::White   => Cell::Black,
+                Cell::Hint(b) => Cell::Hint(!b),
+                Cell::Unknown => Cell::Unknown,
+            };
+        }
+    }
+
+    ///
+    /// Replaces the specifications by the ones the inverted cells satisfy
+    ///
+    /// The specifications of an inverted line cannot be derived from the original
+    /// specifications alone, so they are computed by run-length encoding the
+    /// complement of the current cells. This is thus only meaningful on solved
+    /// grids, unknown cells being counted as white in the inverted grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let mut picross = Picross {
+    ///     height: 1,
+    ///     length: 4,
+    ///     cells: vec![vec![Black, White, White, Black]],
+    ///     row_spec: vec![vec![1, 1]],
+    ///     col_spec: vec![vec![1], vec![], vec![], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// picross.invert_specs();
+    /// assert_eq!(picross.row_spec, vec![vec![2]]);
+    /// assert_eq!(picross.col_spec, vec![vec![], vec![1], vec![1], vec![]]);
+    /// ```
+    ///
+    pub fn invert_specs(&mut self) {
+        let mut inverted = self.clone();
+        inverted.invert_cells();
+        self.row_spec = inverted.cells.iter().map(|r| line_runs(r)).collect();
+        self.col_spec = inverted.transpose().iter().map(|c| line_runs(c)).collect();
+    }
+
+    ///
+    /// Returns the inverted puzzle, with black and white swapped in both the cells
+    /// and the specifications
+    ///
+    /// See `invert_specs` for the limits of inverting the specifications.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    ///
+    /// let picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Black, White], vec![Black, Black]],
+    ///     row_spec: vec![vec![1], vec![2]],
+    ///     col_spec: vec![vec![2], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    ///
+    /// let inverted = picross.inverted();
+    /// assert_eq!(inverted.cells, vec![vec![White, Black], vec![White, White]]);
+    /// assert_eq!(inverted.row_spec, vec![vec![1], vec![]]);
+    /// assert!(inverted.is_valid());
+    /// ```
+    ///
+    pub fn inverted(&self) -> Picross {
+        let mut res = self.clone();
+        res.invert_specs();
+        res.invert_cells();
+        res
+    }
+
+    ///
+    /// Returns the symmetry class of the grid, taking both the cells and the
+    /// specifications into account
+    ///
+    /// When the grid has several symmetries, the most specific class is returned,
+    /// in this order: `AllSymmetries`, `RotationalFull`, `HorizontalReflection`,
+    /// `VerticalReflection`, `DiagonalReflection` and `RotationalHalf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::{Picross, Cell};
+    /// use picross::Cell::{Black, White};
+    /// use picross::symmetry::SymmetryType;
+    ///
+    /// let mut picross = Picross {
+    ///     height: 2,
+    ///     length: 2,
+    ///     cells: vec![vec![Black, White],
+    ///                 vec![White, Black]],
+    ///     row_spec: vec![vec![1], vec![1]],
+    ///     col_spec: vec![vec![1], vec![1]],
+    ///     possible_rows: vec![],
+    ///     possible_cols: vec![],
+    /// };
+    /// assert_eq!(picross.symmetry_type(), SymmetryType::DiagonalReflection);
+    ///
+    /// picross.cells = vec![vec![Black, Black], vec![White, White]];
+    /// picross.row_spec = vec![vec![2], vec![]];
+    /// picross.col_spec = vec![vec![1], vec![1]];
+    /// assert_eq!(picross.symmetry_type(), SymmetryType::HorizontalReflection);
+    ///
+    /// picross.cells = vec![vec![Black, Black], vec![Black, Black]];
+    /// picross.row_spec = vec![vec![2], vec![2]];
+    /// picross.col_spec = vec![vec![2], vec![2]];
+    /// assert_eq!(picross.symmetry_type(), SymmetryType::AllSymmetries);
+    /// ```
+    ///
+    pub fn symmetry_type(&self) -> SymmetryType {
+        let transposed = self.with_parts(self.transpose(), self.col_spec.clone(),
+                                         self.row_spec.clone());
+
+        let rot_full = self.same_grid(&self.rotate_90());
+        let rot_half = self.same_grid(&self.rotate_180());
+        let horizontal = self.same_grid(&self.flip_horizontal());
+        let vertical = self.same_grid(&self.flip_vertical());
+        let diagonal = self.same_grid(&transposed);
+
+        if rot_full && horizontal && vertical && diagonal {
+            SymmetryType::AllSymmetries
+        } else if rot_full {
+            SymmetryType::RotationalFull
+        } else if horizontal {
+            SymmetryType::HorizontalReflection
+        } else if vertical {
+            SymmetryType::VerticalReflection
+        } else if diagonal {
+            SymmetryType::DiagonalReflection
+        } else if rot_half {
+            SymmetryType::RotationalHalf
+        } else {
+            SymmetryType::None
+        }
+    }
+}
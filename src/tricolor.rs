@@ -0,0 +1,168 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+/// A cell of a color nonogram
+///
+/// `Color(0)` is white, and `Color(n)` with `n > 0` is the `n`-th color of the
+/// puzzle.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TricolorCell {
+    Unknown,
+    Color(u8),
+}
+
+/// A color nonogram grid
+///
+/// Each clue is a `(length, color)` pair. Unlike black and white grids, two
+/// consecutive blocks of different colors may touch, while two consecutive
+/// blocks of the same color must be separated by at least one white cell.
+#[derive(Clone, Debug)]
+pub struct TricolorPicross {
+    pub height: usize,
+    pub length: usize,
+
+    pub cells: Vec<Vec<TricolorCell>>,
+
+    pub row_spec: Vec<Vec<(usize, u8)>>,
+    pub col_spec: Vec<Vec<(usize, u8)>>,
+}
+
+/// Returns the `(length, color)` blocks of a line, or `None` if it has an
+/// unknown cell
+fn line_blocks<'a, I: Iterator<Item=&'a TricolorCell>>(line: I) -> Option<Vec<(usize, u8)>> {
+    let mut blocks: Vec<(usize, u8)> = vec![];
+    let mut prev = 0;
+    for c in line {
+        match *c {
+            TricolorCell::Unknown => return None,
+            TricolorCell::Color(0) => prev = 0,
+            TricolorCell::Color(n) => {
+                if n == prev {
+                    blocks.last_mut().expect("Block in progress").0 += 1;
+                } else {
+                    blocks.push((1, n));
+                }
+                prev = n;
+            }
+        }
+    }
+    Some(blocks)
+}
+
+/// Returns the character used to display color `color`, white being a space
+fn color_char(color: u8) -> char {
+    match color {
+        0 => ' ',
+        n @ 1..=26 => (b'a' + n - 1) as char,
+        _ => '*',
+    }
+}
+
+fn spec_to_string(spec: &[(usize, u8)]) -> String {
+    spec.iter()
+        .map(|&(len, color)| format!("{}{}", len, color_char(color)))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+impl TricolorPicross {
+    ///
+    /// Checks whether the grid is completely filled and matches its specifications
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use picross::tricolor::{TricolorCell, TricolorPicross};
+    /// use picross::tricolor::TricolorCell::Color;
+    ///
+    /// let mut picross = TricolorPicross {
+    ///     height: 2,
+    ///     length: 3,
+    ///     cells: vec![vec![Color(1), Color(2), Color(0)],
+    ///                 vec![Color(1), Color(0), Color(1)]],
+    ///     row_spec: vec![vec![(1, 1), (1, 2)], vec![(1, 1), (1, 1)]],
+    ///     col_spec: vec![vec![(2, 1)], vec![(1, 2)], vec![(1, 1)]],
+    /// };
+    /// assert!(picross.is_valid());
+    ///
+    /// picross.cells[1][2] = TricolorCell::Unknown;
+    /// assert!(!picross.is_valid());
+    ///
+    /// picross.cells[1][2] = Color(2);
+    /// assert!(!picross.is_valid());
+    /// ```
+    ///
+    pub fn is_valid(&self) -> bool {
+        if self.cells.len() != self.height || self.row_spec.len() != self.height
+            || self.col_spec.len() != self.length
+            || self.cells.iter().any(|r| r.len() != self.length) {
+            return false;
+        }
+
+        let rows_ok = self.cells.iter().zip(&self.row_spec).all(|(row, spec)| {
+            line_blocks(row.iter()).as_ref() == Some(spec)
+        });
+        rows_ok && self.col_spec.iter().enumerate().all(|(j, spec)| {
+            line_blocks(self.cells.iter().map(|r| &r[j])).as_ref() == Some(spec)
+        })
+    }
+}
+
+/// Displays the grid like a black and white one, colors being shown as letters
+/// (`a` for `Color(1)`, `b` for `Color(2)`...) both in the cells and the clues
+///
+/// # Examples
+///
+/// ```
+/// use picross::tricolor::{TricolorCell, TricolorPicross};
+/// use picross::tricolor::TricolorCell::Color;
+///
+/// let picross = TricolorPicross {
+///     height: 2,
+///     length: 2,
+///     cells: vec![vec![Color(1), Color(2)],
+///                 vec![Color(0), TricolorCell::Unknown]],
+///     row_spec: vec![vec![(1, 1), (1, 2)], vec![(1, 2)]],
+///     col_spec: vec![vec![(1, 1)], vec![(2, 2)]],
+/// };
+///
+/// assert_eq!(format!("{}", picross),
+///            "     |12\n     |ab\n-----+--\n1a 1b|ab\n   1b| ?\n");
+/// ```
+impl Display for TricolorPicross {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let row_spec = self.row_spec.iter().map(|s| spec_to_string(s)).collect::<Vec<String>>();
+        let col_spec = self.col_spec.iter().map(|s| spec_to_string(s)).collect::<Vec<String>>();
+
+        let max_rs_len = row_spec.iter().map(|s| s.len()).max().unwrap_or(0);
+        let max_cs_len = col_spec.iter().map(|s| s.len()).max().unwrap_or(0);
+
+        // Write the header: column specs
+        for i in 0..max_cs_len {
+            f.write_str(&" ".repeat(max_rs_len))?;
+            f.write_char('|')?;
+            for c in &col_spec {
+                f.write_char(c.chars().nth(i.wrapping_sub(max_cs_len - c.len())).unwrap_or(' '))?;
+            }
+            f.write_char('\n')?;
+        }
+
+        // Write header separator
+        f.write_str(&"-".repeat(max_rs_len))?;
+        f.write_char('+')?;
+        f.write_str(&"-".repeat(self.length))?;
+        f.write_char('\n')?;
+
+        for (spec, row) in row_spec.iter().zip(&self.cells) {
+            f.write_str(&" ".repeat(max_rs_len - spec.len()))?;
+            f.write_str(spec)?;
+            f.write_char('|')?;
+            f.write_str(&row.iter().map(|c| match *c {
+                TricolorCell::Unknown => '?',
+                TricolorCell::Color(n) => color_char(n),
+            }).collect::<String>())?;
+            f.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+}